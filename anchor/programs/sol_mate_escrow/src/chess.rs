@@ -0,0 +1,718 @@
+//! Minimal chess rules engine used to verify move transcripts on-chain.
+//!
+//! Only depends on `core`, so it works in `no_std` contexts and never allocates.
+//! Squares are numbered 0 (a1) to 63 (h8). Moves use a compact UCI encoding:
+//! bits 0-5 from square, bits 6-11 to square, bits 12-14 promotion piece
+//! (0 for none, otherwise `KNIGHT`, `BISHOP`, `ROOK` or `QUEEN`).
+
+pub const EMPTY: u8 = 0;
+pub const PAWN: u8 = 1;
+pub const KNIGHT: u8 = 2;
+pub const BISHOP: u8 = 3;
+pub const ROOK: u8 = 4;
+pub const QUEEN: u8 = 5;
+pub const KING: u8 = 6;
+
+/// Color flag OR'd into a piece code for black pieces.
+pub const BLACK: u8 = 8;
+const TYPE_MASK: u8 = 7;
+
+pub const WHITE_KINGSIDE: u8 = 1;
+pub const WHITE_QUEENSIDE: u8 = 2;
+pub const BLACK_KINGSIDE: u8 = 4;
+pub const BLACK_QUEENSIDE: u8 = 8;
+pub const ALL_CASTLING: u8 = 15;
+
+/// Marker for "no en passant square".
+pub const NO_SQUARE: u8 = 64;
+
+const KNIGHT_OFFSETS: [(i8, i8); 8] = [
+    (1, 2), (2, 1), (2, -1), (1, -2), (-1, -2), (-2, -1), (-2, 1), (-1, 2),
+];
+const KING_OFFSETS: [(i8, i8); 8] = [
+    (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1),
+];
+const ROOK_DIRECTIONS: [(i8, i8); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+const BISHOP_DIRECTIONS: [(i8, i8); 4] = [(1, 1), (-1, 1), (-1, -1), (1, -1)];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Color {
+    White,
+    Black,
+}
+
+impl Color {
+    pub fn opposite(self) -> Color {
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        }
+    }
+
+    fn flag(self) -> u8 {
+        match self {
+            Color::White => 0,
+            Color::Black => BLACK,
+        }
+    }
+
+    /// Rank direction pawns of this color move in.
+    fn forward(self) -> i8 {
+        match self {
+            Color::White => 1,
+            Color::Black => -1,
+        }
+    }
+
+    fn home_rank(self) -> i8 {
+        match self {
+            Color::White => 0,
+            Color::Black => 7,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Move {
+    pub from: u8,
+    pub to: u8,
+    pub promotion: u8,
+}
+
+impl Move {
+    /// Decode a compact UCI move. Returns `None` for out-of-range fields.
+    pub fn decode(raw: u16) -> Option<Move> {
+        let promotion = ((raw >> 12) & 0x7) as u8;
+        if raw & 0x8000 != 0 || !(promotion == EMPTY || (KNIGHT..=QUEEN).contains(&promotion)) {
+            return None;
+        }
+        Some(Move {
+            from: (raw & 0x3f) as u8,
+            to: ((raw >> 6) & 0x3f) as u8,
+            promotion,
+        })
+    }
+
+    pub fn encode(self) -> u16 {
+        self.from as u16 | (self.to as u16) << 6 | (self.promotion as u16) << 12
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct IllegalMove;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GameStatus {
+    Ongoing,
+    Checkmate,
+    Stalemate,
+    InsufficientMaterial,
+}
+
+fn file(square: u8) -> i8 {
+    (square % 8) as i8
+}
+
+fn rank(square: u8) -> i8 {
+    (square / 8) as i8
+}
+
+fn square_at(file: i8, rank: i8) -> Option<u8> {
+    if (0..8).contains(&file) && (0..8).contains(&rank) {
+        Some((rank * 8 + file) as u8)
+    } else {
+        None
+    }
+}
+
+fn piece_type(piece: u8) -> u8 {
+    piece & TYPE_MASK
+}
+
+fn piece_color(piece: u8) -> Color {
+    if piece & BLACK != 0 {
+        Color::Black
+    } else {
+        Color::White
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Position {
+    pub board: [u8; 64],
+    pub side_to_move: Color,
+    pub castling: u8,
+    pub en_passant: u8,
+}
+
+impl Position {
+    /// The standard starting position.
+    pub fn start() -> Position {
+        const BACK_RANK: [u8; 8] = [ROOK, KNIGHT, BISHOP, QUEEN, KING, BISHOP, KNIGHT, ROOK];
+        let mut board = [EMPTY; 64];
+        for (f, &piece) in BACK_RANK.iter().enumerate() {
+            board[f] = piece;
+            board[8 + f] = PAWN;
+            board[48 + f] = PAWN | BLACK;
+            board[56 + f] = piece | BLACK;
+        }
+        Position {
+            board,
+            side_to_move: Color::White,
+            castling: ALL_CASTLING,
+            en_passant: NO_SQUARE,
+        }
+    }
+
+    fn own_piece(&self, square: u8, color: Color) -> bool {
+        let piece = self.board[square as usize];
+        piece != EMPTY && piece_color(piece) == color
+    }
+
+    fn has_piece(&self, file: i8, rank: i8, piece: u8) -> bool {
+        matches!(square_at(file, rank), Some(sq) if self.board[sq as usize] == piece)
+    }
+
+    /// Whether any piece of color `by` attacks `square`.
+    pub fn is_attacked(&self, square: u8, by: Color) -> bool {
+        let (f, r) = (file(square), rank(square));
+        let flag = by.flag();
+
+        // Pawns attack diagonally forward, so look one rank behind the square
+        let pawn_rank = r - by.forward();
+        if self.has_piece(f - 1, pawn_rank, PAWN | flag) || self.has_piece(f + 1, pawn_rank, PAWN | flag) {
+            return true;
+        }
+
+        if KNIGHT_OFFSETS.iter().any(|&(df, dr)| self.has_piece(f + df, r + dr, KNIGHT | flag)) {
+            return true;
+        }
+
+        if KING_OFFSETS.iter().any(|&(df, dr)| self.has_piece(f + df, r + dr, KING | flag)) {
+            return true;
+        }
+
+        let slider_hits = |directions: &[(i8, i8); 4], slider: u8| {
+            directions.iter().any(|&(df, dr)| {
+                let (mut cf, mut cr) = (f + df, r + dr);
+                while let Some(sq) = square_at(cf, cr) {
+                    let piece = self.board[sq as usize];
+                    if piece != EMPTY {
+                        return piece == slider | flag || piece == QUEEN | flag;
+                    }
+                    cf += df;
+                    cr += dr;
+                }
+                false
+            })
+        };
+
+        slider_hits(&ROOK_DIRECTIONS, ROOK) || slider_hits(&BISHOP_DIRECTIONS, BISHOP)
+    }
+
+    fn king_square(&self, color: Color) -> Option<u8> {
+        let king = KING | color.flag();
+        (0..64u8).find(|&sq| self.board[sq as usize] == king)
+    }
+
+    /// Whether the side to move is in check.
+    pub fn in_check(&self) -> bool {
+        match self.king_square(self.side_to_move) {
+            Some(sq) => self.is_attacked(sq, self.side_to_move.opposite()),
+            None => false,
+        }
+    }
+
+    /// Every square strictly between `from` and `to` is empty. Assumes a straight or diagonal line.
+    fn path_clear(&self, from: u8, to: u8) -> bool {
+        let df = (file(to) - file(from)).signum();
+        let dr = (rank(to) - rank(from)).signum();
+        let (mut f, mut r) = (file(from) + df, rank(from) + dr);
+        while let Some(sq) = square_at(f, r) {
+            if sq == to {
+                return true;
+            }
+            if self.board[sq as usize] != EMPTY {
+                return false;
+            }
+            f += df;
+            r += dr;
+        }
+        false
+    }
+
+    fn castling_allowed(&self, king_from: u8, king_to: u8) -> bool {
+        let color = self.side_to_move;
+        let home = color.home_rank();
+        if rank(king_from) != home || file(king_from) != 4 || rank(king_to) != home {
+            return false;
+        }
+
+        let kingside = file(king_to) == 6;
+        let right = match (color, kingside) {
+            (Color::White, true) => WHITE_KINGSIDE,
+            (Color::White, false) => WHITE_QUEENSIDE,
+            (Color::Black, true) => BLACK_KINGSIDE,
+            (Color::Black, false) => BLACK_QUEENSIDE,
+        };
+        let rook_file = if kingside { 7 } else { 0 };
+        if self.castling & right == 0 || !self.has_piece(rook_file, home, ROOK | color.flag()) {
+            return false;
+        }
+
+        // Squares between king and rook must be empty
+        let rook_square = (home * 8 + rook_file) as u8;
+        if !self.path_clear(king_from, rook_square) {
+            return false;
+        }
+
+        // King may not castle out of, through, or into check
+        let enemy = color.opposite();
+        let step: i8 = if kingside { 1 } else { -1 };
+        (0..=2).all(|i| {
+            let sq = (home * 8 + 4 + step * i) as u8;
+            !self.is_attacked(sq, enemy)
+        })
+    }
+
+    /// Movement rules for the piece on `mv.from`, ignoring whether the mover's king ends up in check.
+    fn is_pseudo_legal(&self, mv: Move) -> bool {
+        if mv.from >= 64 || mv.to >= 64 || mv.from == mv.to {
+            return false;
+        }
+
+        let color = self.side_to_move;
+        let piece = self.board[mv.from as usize];
+        if piece == EMPTY || piece_color(piece) != color || self.own_piece(mv.to, color) {
+            return false;
+        }
+
+        let kind = piece_type(piece);
+        let target = self.board[mv.to as usize];
+        let df = file(mv.to) - file(mv.from);
+        let dr = rank(mv.to) - rank(mv.from);
+
+        // Promotion piece is required exactly when a pawn reaches the last rank
+        let promotes = kind == PAWN && rank(mv.to) == color.opposite().home_rank();
+        if promotes != (mv.promotion != EMPTY) {
+            return false;
+        }
+
+        match kind {
+            PAWN => {
+                let forward = color.forward();
+                let start_rank = color.home_rank() + forward;
+                if df == 0 && target == EMPTY {
+                    if dr == forward {
+                        return true;
+                    }
+                    let over = (mv.from as i8 + 8 * forward) as u8;
+                    return dr == 2 * forward
+                        && rank(mv.from) == start_rank
+                        && self.board[over as usize] == EMPTY;
+                }
+                df.abs() == 1
+                    && dr == forward
+                    && (target != EMPTY || (mv.to == self.en_passant && self.en_passant != NO_SQUARE))
+            }
+            KNIGHT => (df.abs() == 1 && dr.abs() == 2) || (df.abs() == 2 && dr.abs() == 1),
+            BISHOP => df.abs() == dr.abs() && self.path_clear(mv.from, mv.to),
+            ROOK => (df == 0 || dr == 0) && self.path_clear(mv.from, mv.to),
+            QUEEN => {
+                (df == 0 || dr == 0 || df.abs() == dr.abs()) && self.path_clear(mv.from, mv.to)
+            }
+            KING => {
+                if df.abs() <= 1 && dr.abs() <= 1 {
+                    return true;
+                }
+                dr == 0 && df.abs() == 2 && target == EMPTY && self.castling_allowed(mv.from, mv.to)
+            }
+            _ => false,
+        }
+    }
+
+    /// Apply a pseudo-legal move without any validation.
+    fn apply(&mut self, mv: Move) {
+        let color = self.side_to_move;
+        let piece = self.board[mv.from as usize];
+        let kind = piece_type(piece);
+        let forward = color.forward();
+
+        // En passant capture removes the pawn behind the destination square
+        if kind == PAWN && mv.to == self.en_passant && file(mv.to) != file(mv.from) {
+            let captured = (mv.to as i8 - 8 * forward) as u8;
+            self.board[captured as usize] = EMPTY;
+        }
+
+        self.board[mv.from as usize] = EMPTY;
+        self.board[mv.to as usize] = if mv.promotion != EMPTY {
+            mv.promotion | color.flag()
+        } else {
+            piece
+        };
+
+        // Castling also moves the rook
+        if kind == KING && (file(mv.to) - file(mv.from)).abs() == 2 {
+            let home = color.home_rank();
+            let (rook_from, rook_to) = if file(mv.to) == 6 { (7, 5) } else { (0, 3) };
+            self.board[(home * 8 + rook_from) as usize] = EMPTY;
+            self.board[(home * 8 + rook_to) as usize] = ROOK | color.flag();
+        }
+
+        // Any move from or to a king/rook home square loses the matching rights
+        for square in [mv.from, mv.to] {
+            self.castling &= !match square {
+                4 => WHITE_KINGSIDE | WHITE_QUEENSIDE,
+                7 => WHITE_KINGSIDE,
+                0 => WHITE_QUEENSIDE,
+                60 => BLACK_KINGSIDE | BLACK_QUEENSIDE,
+                63 => BLACK_KINGSIDE,
+                56 => BLACK_QUEENSIDE,
+                _ => 0,
+            };
+        }
+
+        self.en_passant = if kind == PAWN && (rank(mv.to) - rank(mv.from)).abs() == 2 {
+            (mv.from as i8 + 8 * forward) as u8
+        } else {
+            NO_SQUARE
+        };
+
+        self.side_to_move = color.opposite();
+    }
+
+    /// The position after `mv`, or `None` if the move is illegal.
+    fn after(&self, mv: Move) -> Option<Position> {
+        if !self.is_pseudo_legal(mv) {
+            return None;
+        }
+        let mut next = *self;
+        next.apply(mv);
+
+        // The mover's king must not be left in check
+        let mover = self.side_to_move;
+        match next.king_square(mover) {
+            Some(king) if !next.is_attacked(king, mover.opposite()) => Some(next),
+            _ => None,
+        }
+    }
+
+    /// Play `mv` for the side to move, rejecting illegal moves.
+    pub fn play(&mut self, mv: Move) -> Result<(), IllegalMove> {
+        *self = self.after(mv).ok_or(IllegalMove)?;
+        Ok(())
+    }
+
+    /// Whether the side to move has at least one legal move.
+    pub fn has_legal_move(&self) -> bool {
+        let color = self.side_to_move;
+        let is_legal = |from: u8, to_file: i8, to_rank: i8| {
+            let Some(to) = square_at(to_file, to_rank) else {
+                return false;
+            };
+            // Any promotion piece is as legal as any other, so try a queen
+            let promotes = piece_type(self.board[from as usize]) == PAWN
+                && to_rank == color.opposite().home_rank();
+            let promotion = if promotes { QUEEN } else { EMPTY };
+            self.after(Move { from, to, promotion }).is_some()
+        };
+
+        (0..64u8).filter(|&sq| self.own_piece(sq, color)).any(|from| {
+            let (f, r) = (file(from), rank(from));
+            let sliding = |directions: &[(i8, i8); 4]| {
+                directions.iter().any(|&(df, dr)| {
+                    (1..8).any(|i| is_legal(from, f + df * i, r + dr * i))
+                })
+            };
+
+            match piece_type(self.board[from as usize]) {
+                PAWN => {
+                    let dr = color.forward();
+                    [(0, dr), (0, 2 * dr), (-1, dr), (1, dr)]
+                        .iter()
+                        .any(|&(df, dr)| is_legal(from, f + df, r + dr))
+                }
+                KNIGHT => KNIGHT_OFFSETS.iter().any(|&(df, dr)| is_legal(from, f + df, r + dr)),
+                BISHOP => sliding(&BISHOP_DIRECTIONS),
+                ROOK => sliding(&ROOK_DIRECTIONS),
+                QUEEN => sliding(&ROOK_DIRECTIONS) || sliding(&BISHOP_DIRECTIONS),
+                KING => {
+                    KING_OFFSETS.iter().any(|&(df, dr)| is_legal(from, f + df, r + dr))
+                        || is_legal(from, f + 2, r)
+                        || is_legal(from, f - 2, r)
+                }
+                _ => false,
+            }
+        })
+    }
+
+    /// Neither side can possibly deliver mate: bare kings, a single minor piece,
+    /// or only bishops that all stand on the same square color.
+    pub fn insufficient_material(&self) -> bool {
+        let mut knights = 0;
+        let mut light_bishops = 0;
+        let mut dark_bishops = 0;
+
+        for sq in 0..64u8 {
+            match piece_type(self.board[sq as usize]) {
+                EMPTY | KING => {}
+                KNIGHT => knights += 1,
+                BISHOP if (file(sq) + rank(sq)) % 2 == 0 => dark_bishops += 1,
+                BISHOP => light_bishops += 1,
+                _ => return false,
+            }
+        }
+
+        let bishops = light_bishops + dark_bishops;
+        match knights {
+            0 => bishops <= 1 || light_bishops == 0 || dark_bishops == 0,
+            1 => bishops == 0,
+            _ => false,
+        }
+    }
+
+    pub fn status(&self) -> GameStatus {
+        if !self.has_legal_move() {
+            if self.in_check() {
+                GameStatus::Checkmate
+            } else {
+                GameStatus::Stalemate
+            }
+        } else if self.insufficient_material() {
+            GameStatus::InsufficientMaterial
+        } else {
+            GameStatus::Ongoing
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(name: &str) -> u8 {
+        let bytes = name.as_bytes();
+        (bytes[1] - b'1') * 8 + (bytes[0] - b'a')
+    }
+
+    /// Parse a UCI move such as `e2e4` or `a7a8q`.
+    fn uci(text: &str) -> Move {
+        let promotion = match text.as_bytes().get(4) {
+            None => EMPTY,
+            Some(b'n') => KNIGHT,
+            Some(b'b') => BISHOP,
+            Some(b'r') => ROOK,
+            Some(b'q') => QUEEN,
+            Some(_) => panic!("bad promotion in {text}"),
+        };
+        Move { from: square(&text[..2]), to: square(&text[2..4]), promotion }
+    }
+
+    fn position(pieces: &[(&str, u8)], side_to_move: Color, castling: u8) -> Position {
+        let mut board = [EMPTY; 64];
+        for &(name, piece) in pieces {
+            board[square(name) as usize] = piece;
+        }
+        Position { board, side_to_move, castling, en_passant: NO_SQUARE }
+    }
+
+    fn play_all(position: &mut Position, moves: &[&str]) {
+        for text in moves {
+            position.play(uci(text)).unwrap_or_else(|_| panic!("{text} should be legal"));
+        }
+    }
+
+    fn is_legal(position: &Position, text: &str) -> bool {
+        let mut next = *position;
+        next.play(uci(text)).is_ok()
+    }
+
+    #[test]
+    fn move_encoding_round_trips() {
+        let mv = uci("a7a8q");
+        assert_eq!(Move::decode(mv.encode()), Some(mv));
+        // Pawns and kings are not promotion pieces, and bit 15 must be clear
+        assert_eq!(Move::decode(1 << 12), None);
+        assert_eq!(Move::decode(6 << 12), None);
+        assert_eq!(Move::decode(0x8000), None);
+    }
+
+    #[test]
+    fn castles_kingside_and_moves_the_rook() {
+        let mut position = Position::start();
+        play_all(&mut position, &["e2e4", "e7e5", "g1f3", "b8c6", "f1c4", "g8f6", "e1g1"]);
+        assert_eq!(position.board[square("g1") as usize], KING);
+        assert_eq!(position.board[square("f1") as usize], ROOK);
+        assert_eq!(position.board[square("h1") as usize], EMPTY);
+        assert_eq!(position.castling, BLACK_KINGSIDE | BLACK_QUEENSIDE);
+    }
+
+    #[test]
+    fn castling_needs_an_empty_path() {
+        let position = Position::start();
+        assert!(!is_legal(&position, "e1g1"));
+        assert!(!is_legal(&position, "e1c1"));
+    }
+
+    #[test]
+    fn moving_a_rook_loses_its_castling_right() {
+        let mut position = position(
+            &[("e1", KING), ("a1", ROOK), ("h1", ROOK), ("e8", KING | BLACK)],
+            Color::White,
+            WHITE_KINGSIDE | WHITE_QUEENSIDE,
+        );
+        play_all(&mut position, &["h1h2", "e8d8", "h2h1", "d8e8"]);
+        assert_eq!(position.castling, WHITE_QUEENSIDE);
+        assert!(!is_legal(&position, "e1g1"));
+        assert!(is_legal(&position, "e1c1"));
+    }
+
+    #[test]
+    fn moving_the_king_loses_both_castling_rights() {
+        let mut position = position(
+            &[("e1", KING), ("a1", ROOK), ("h1", ROOK), ("e8", KING | BLACK)],
+            Color::White,
+            WHITE_KINGSIDE | WHITE_QUEENSIDE,
+        );
+        play_all(&mut position, &["e1f1", "e8d8", "f1e1", "d8e8"]);
+        assert_eq!(position.castling, 0);
+        assert!(!is_legal(&position, "e1g1"));
+        assert!(!is_legal(&position, "e1c1"));
+    }
+
+    #[test]
+    fn capturing_a_rook_removes_its_castling_right() {
+        let mut position = position(
+            &[("e1", KING), ("h1", ROOK), ("e8", KING | BLACK), ("h8", ROOK | BLACK)],
+            Color::White,
+            WHITE_KINGSIDE | BLACK_KINGSIDE,
+        );
+        play_all(&mut position, &["h1h8"]);
+        assert_eq!(position.castling, 0);
+    }
+
+    #[test]
+    fn cannot_castle_out_of_through_or_into_check() {
+        let pieces = |attacker: &'static str| {
+            position(
+                &[("e1", KING), ("h1", ROOK), ("a8", KING | BLACK), (attacker, ROOK | BLACK)],
+                Color::White,
+                WHITE_KINGSIDE,
+            )
+        };
+        assert!(is_legal(&pieces("d8"), "e1g1"));
+        assert!(!is_legal(&pieces("e8"), "e1g1"));
+        assert!(!is_legal(&pieces("f8"), "e1g1"));
+        assert!(!is_legal(&pieces("g8"), "e1g1"));
+    }
+
+    #[test]
+    fn en_passant_captures_the_passed_pawn() {
+        let mut position = Position::start();
+        play_all(&mut position, &["e2e4", "a7a6", "e4e5", "d7d5"]);
+        assert_eq!(position.en_passant, square("d6"));
+        play_all(&mut position, &["e5d6"]);
+        assert_eq!(position.board[square("d6") as usize], PAWN);
+        assert_eq!(position.board[square("d5") as usize], EMPTY);
+        assert_eq!(position.en_passant, NO_SQUARE);
+    }
+
+    #[test]
+    fn en_passant_expires_after_one_move() {
+        let mut position = Position::start();
+        play_all(&mut position, &["e2e4", "a7a6", "e4e5", "d7d5", "h2h3", "a6a5"]);
+        assert!(!is_legal(&position, "e5d6"));
+    }
+
+    #[test]
+    fn promotion_is_required_on_the_last_rank_only() {
+        let position = position(
+            &[("a7", PAWN), ("e2", PAWN), ("e1", KING), ("h8", KING | BLACK)],
+            Color::White,
+            0,
+        );
+        assert!(!is_legal(&position, "a7a8"));
+        assert!(!is_legal(&position, "e2e3q"));
+        assert!(is_legal(&position, "a7a8n"));
+
+        let mut promoted = position;
+        play_all(&mut promoted, &["a7a8q"]);
+        assert_eq!(promoted.board[square("a8") as usize], QUEEN);
+        assert_eq!(promoted.board[square("a7") as usize], EMPTY);
+        // The new queen gives check along the back rank
+        assert!(promoted.in_check());
+    }
+
+    #[test]
+    fn fools_mate_is_checkmate() {
+        let mut position = Position::start();
+        play_all(&mut position, &["f2f3", "e7e5", "g2g4"]);
+        assert_eq!(position.status(), GameStatus::Ongoing);
+        play_all(&mut position, &["d8h4"]);
+        assert_eq!(position.status(), GameStatus::Checkmate);
+    }
+
+    #[test]
+    fn check_with_an_escape_is_not_mate() {
+        let position = position(
+            &[("e1", KING), ("e8", ROOK | BLACK), ("a8", KING | BLACK)],
+            Color::White,
+            0,
+        );
+        assert!(position.in_check());
+        assert_eq!(position.status(), GameStatus::Ongoing);
+    }
+
+    #[test]
+    fn king_without_moves_and_not_in_check_is_stalemate() {
+        let position = position(
+            &[("a8", KING | BLACK), ("b6", QUEEN), ("e1", KING)],
+            Color::Black,
+            0,
+        );
+        assert!(!position.in_check());
+        assert_eq!(position.status(), GameStatus::Stalemate);
+    }
+
+    #[test]
+    fn bare_kings_are_insufficient_material() {
+        let position = position(&[("e1", KING), ("e8", KING | BLACK), ("c1", BISHOP)], Color::White, 0);
+        assert_eq!(position.status(), GameStatus::InsufficientMaterial);
+    }
+
+    #[test]
+    fn pinned_piece_cannot_move() {
+        let position = position(
+            &[("e1", KING), ("e2", KNIGHT), ("e8", ROOK | BLACK), ("a8", KING | BLACK)],
+            Color::White,
+            0,
+        );
+        assert!(!is_legal(&position, "e2c3"));
+        assert!(is_legal(&position, "e1f1"));
+    }
+
+    #[test]
+    fn king_cannot_move_into_check() {
+        let position = position(
+            &[("e1", KING), ("d8", ROOK | BLACK), ("a8", KING | BLACK)],
+            Color::White,
+            0,
+        );
+        assert!(!is_legal(&position, "e1d1"));
+        assert!(!is_legal(&position, "e1d2"));
+        assert!(is_legal(&position, "e1f2"));
+    }
+
+    #[test]
+    fn check_must_be_answered() {
+        let position = position(
+            &[("e1", KING), ("a2", PAWN), ("d4", ROOK), ("e8", ROOK | BLACK), ("a8", KING | BLACK)],
+            Color::White,
+            0,
+        );
+        assert!(!is_legal(&position, "a2a3"));
+        assert!(!is_legal(&position, "d4d5"));
+        assert!(is_legal(&position, "d4e4"));
+        assert!(is_legal(&position, "e1f1"));
+    }
+}
@@ -0,0 +1,214 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::load_instruction_at_checked;
+use crate::errors::EscrowError;
+
+// Layout of the ed25519 precompile instruction data:
+// [num_signatures: u8][padding: u8][Ed25519SignatureOffsets; num_signatures]...
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
+const PUBKEY_SERIALIZED_SIZE: usize = 32;
+
+/// Instruction index value meaning "data lives in the precompile instruction itself".
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+/// Mirror of the precompile's `Ed25519SignatureOffsets` entry.
+struct SignatureOffsets {
+    signature_instruction_index: u16,
+    public_key_offset: usize,
+    public_key_instruction_index: u16,
+    message_data_offset: usize,
+    message_data_size: usize,
+    message_instruction_index: u16,
+}
+
+impl SignatureOffsets {
+    fn parse(data: &[u8], index: usize) -> Option<Self> {
+        let start = SIGNATURE_OFFSETS_START + index * SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+        Some(Self {
+            signature_instruction_index: read_u16(data, start + 2)?,
+            public_key_offset: read_u16(data, start + 4)? as usize,
+            public_key_instruction_index: read_u16(data, start + 6)?,
+            message_data_offset: read_u16(data, start + 8)? as usize,
+            message_data_size: read_u16(data, start + 10)? as usize,
+            message_instruction_index: read_u16(data, start + 12)?,
+        })
+    }
+    
+    /// Whether this entry covers a signature by `signer` over exactly `message`.
+    fn matches(&self, data: &[u8], signer: &Pubkey, message: &[u8]) -> bool {
+        // Everything must be embedded in the precompile instruction, otherwise the
+        // precompile may have verified bytes other than the ones inspected here.
+        if self.signature_instruction_index != CURRENT_INSTRUCTION
+            || self.public_key_instruction_index != CURRENT_INSTRUCTION
+            || self.message_instruction_index != CURRENT_INSTRUCTION
+        {
+            return false;
+        }
+        
+        let pubkey_end = self.public_key_offset + PUBKEY_SERIALIZED_SIZE;
+        let message_end = self.message_data_offset + self.message_data_size;
+        data.get(self.public_key_offset..pubkey_end) == Some(signer.as_ref())
+            && data.get(self.message_data_offset..message_end) == Some(message)
+    }
+}
+
+/// Returns true if the ed25519 precompile instruction `data` verified a signature
+/// by `signer` over exactly `message`.
+fn precompile_verified(data: &[u8], signer: &Pubkey, message: &[u8]) -> bool {
+    let num_signatures = data.first().copied().unwrap_or(0) as usize;
+    
+    (0..num_signatures).any(|i| {
+        matches!(
+            SignatureOffsets::parse(data, i),
+            Some(offsets) if offsets.matches(data, signer, message)
+        )
+    })
+}
+
+/// Require that some ed25519 precompile instruction in the current transaction
+/// verified a signature by `signer` over `message`.
+pub fn require_signed_message(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let mut index = 0;
+    while let Ok(ix) = load_instruction_at_checked(index, instructions_sysvar) {
+        if ix.program_id == ed25519_program::ID && precompile_verified(&ix.data, signer, message) {
+            return Ok(());
+        }
+        index += 1;
+    }
+    
+    err!(EscrowError::MissingSignatureVerification)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::sysvar::instructions::{
+        construct_instructions_data, BorrowedInstruction, ID as INSTRUCTIONS_SYSVAR_ID,
+    };
+    
+    const SIGNATURE_SERIALIZED_SIZE: usize = 64;
+    
+    /// Precompile instruction data verifying `entries`, with every offset pointing
+    /// into the instruction itself unless `instruction_index` says otherwise.
+    fn precompile_data(entries: &[(Pubkey, &[u8])], instruction_index: u16) -> Vec<u8> {
+        let mut data = vec![entries.len() as u8, 0];
+        let mut offset = SIGNATURE_OFFSETS_START + entries.len() * SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+        let mut payload = Vec::new();
+        for (signer, message) in entries {
+            let public_key_offset = offset;
+            let signature_offset = public_key_offset + PUBKEY_SERIALIZED_SIZE;
+            let message_data_offset = signature_offset + SIGNATURE_SERIALIZED_SIZE;
+            for value in [
+                signature_offset as u16,
+                instruction_index,
+                public_key_offset as u16,
+                instruction_index,
+                message_data_offset as u16,
+                message.len() as u16,
+                instruction_index,
+            ] {
+                data.extend_from_slice(&value.to_le_bytes());
+            }
+            payload.extend_from_slice(signer.as_ref());
+            payload.extend_from_slice(&[0u8; SIGNATURE_SERIALIZED_SIZE]);
+            payload.extend_from_slice(message);
+            offset = message_data_offset + message.len();
+        }
+        data.extend_from_slice(&payload);
+        data
+    }
+    
+    /// Run `f` with an instructions sysvar account holding `instructions` as `(program, data)`.
+    fn with_instructions<R>(instructions: &[(Pubkey, Vec<u8>)], f: impl FnOnce(&AccountInfo) -> R) -> R {
+        let borrowed: Vec<BorrowedInstruction> = instructions
+            .iter()
+            .map(|(program_id, data)| BorrowedInstruction { program_id, accounts: vec![], data })
+            .collect();
+        let mut data = construct_instructions_data(&borrowed);
+        let mut lamports = 0;
+        let key = INSTRUCTIONS_SYSVAR_ID;
+        let owner = Pubkey::default();
+        let account = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        f(&account)
+    }
+    
+    #[test]
+    fn accepts_signature_by_signer_over_message() {
+        let signer = Pubkey::new_unique();
+        let data = precompile_data(&[(signer, b"result")], CURRENT_INSTRUCTION);
+        assert!(precompile_verified(&data, &signer, b"result"));
+    }
+    
+    #[test]
+    fn rejects_other_signer_or_message() {
+        let signer = Pubkey::new_unique();
+        let data = precompile_data(&[(signer, b"result")], CURRENT_INSTRUCTION);
+        assert!(!precompile_verified(&data, &Pubkey::new_unique(), b"result"));
+        assert!(!precompile_verified(&data, &signer, b"resulT"));
+        assert!(!precompile_verified(&data, &signer, b"resul"));
+        assert!(!precompile_verified(&data, &signer, b"results"));
+    }
+    
+    #[test]
+    fn finds_signature_among_several() {
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let data = precompile_data(&[(first, b"one"), (second, b"two")], CURRENT_INSTRUCTION);
+        assert!(precompile_verified(&data, &first, b"one"));
+        assert!(precompile_verified(&data, &second, b"two"));
+        assert!(!precompile_verified(&data, &second, b"one"));
+    }
+    
+    #[test]
+    fn rejects_data_held_by_other_instructions() {
+        let signer = Pubkey::new_unique();
+        let data = precompile_data(&[(signer, b"result")], 0);
+        assert!(!precompile_verified(&data, &signer, b"result"));
+    }
+    
+    #[test]
+    fn rejects_malformed_data() {
+        let signer = Pubkey::new_unique();
+        assert!(!precompile_verified(&[], &signer, b"result"));
+        
+        // Entries claimed but missing are skipped without panicking
+        let mut data = precompile_data(&[(signer, b"result")], CURRENT_INSTRUCTION);
+        data[0] = 3;
+        assert!(precompile_verified(&data, &signer, b"result"));
+        
+        // Offsets pointing past the end of the data match nothing
+        data.truncate(SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SERIALIZED_SIZE + 10);
+        assert!(!precompile_verified(&data, &signer, b"result"));
+    }
+    
+    #[test]
+    fn require_signed_message_finds_precompile_instruction() {
+        let signer = Pubkey::new_unique();
+        let instructions = [
+            (Pubkey::new_unique(), vec![1, 2, 3]),
+            (ed25519_program::ID, precompile_data(&[(signer, b"result")], CURRENT_INSTRUCTION)),
+        ];
+        with_instructions(&instructions, |sysvar| {
+            assert!(require_signed_message(sysvar, &signer, b"result").is_ok());
+            assert!(require_signed_message(sysvar, &signer, b"other").is_err());
+        });
+    }
+    
+    #[test]
+    fn require_signed_message_ignores_other_programs() {
+        let signer = Pubkey::new_unique();
+        let instructions = [(Pubkey::new_unique(), precompile_data(&[(signer, b"result")], CURRENT_INSTRUCTION))];
+        with_instructions(&instructions, |sysvar| {
+            assert!(require_signed_message(sysvar, &signer, b"result").is_err());
+        });
+    }
+}
@@ -2,10 +2,10 @@ use anchor_lang::prelude::*;
 
 #[error_code]
 pub enum EscrowError {
-    #[msg("Invalid stake tier. Must be 0 (0.5 SOL), 1 (1 SOL), 2 (5 SOL), or 3 (10 SOL)")]
+    #[msg("Stake tier is not enabled in the config's tier table")]
     InvalidStakeTier,
     
-    #[msg("Invalid timestamp: must be within 30 seconds of current time")]
+    #[msg("Invalid timestamp: join deadline is outside the allowed join window")]
     InvalidTimestamp,
     
     #[msg("Match is not in Open status")]
@@ -55,4 +55,232 @@ pub enum EscrowError {
     
     #[msg("Caller is not a player in this match")]
     NotAPlayer,
+    
+    #[msg("No result has been proposed for this match")]
+    ResultNotProposed,
+    
+    #[msg("Only the opponent of the proposer can confirm or contest a result")]
+    CannotRespondToOwnProposal,
+    
+    #[msg("Challenge window is still open")]
+    ChallengeWindowOpen,
+    
+    #[msg("Challenge window has closed")]
+    ChallengeWindowClosed,
+    
+    #[msg("Challenge window is outside the allowed range")]
+    InvalidChallengeWindow,
+    
+    #[msg("No referee is configured")]
+    RefereeNotConfigured,
+    
+    #[msg("No matching ed25519 signature verification found in transaction")]
+    MissingSignatureVerification,
+    
+    #[msg("End reason is not valid for this result")]
+    InvalidEndReason,
+    
+    #[msg("No arbiter is configured")]
+    ArbiterNotConfigured,
+    
+    #[msg("Only the arbiter can resolve disputes")]
+    OnlyArbiter,
+    
+    #[msg("Match result cannot be disputed in its current status")]
+    MatchNotDisputable,
+    
+    #[msg("Match is not in Disputed status")]
+    MatchNotDisputed,
+    
+    #[msg("Move encoding is invalid")]
+    InvalidMoveEncoding,
+    
+    #[msg("Move is illegal in the current position")]
+    IllegalMove,
+    
+    #[msg("Replayed position is not checkmate")]
+    NotCheckmate,
+    
+    #[msg("Replay does not belong to this match or prover")]
+    ReplayMismatch,
+    
+    #[msg("Fee basis points out of range")]
+    InvalidFeeBps,
+    
+    #[msg("Match is not in Drawn status")]
+    MatchNotDrawn,
+    
+    #[msg("Seconds per move is outside the allowed range")]
+    InvalidMoveTime,
+    
+    #[msg("Correspondence matches must pass their game account")]
+    MissingGameAccount,
+    
+    #[msg("Results of correspondence matches are decided on the board")]
+    CorrespondenceMatch,
+    
+    #[msg("It is not this player's turn")]
+    NotYourTurn,
+    
+    #[msg("Move deadline has passed")]
+    MoveDeadlinePassed,
+    
+    #[msg("Move deadline has not passed yet")]
+    MoveDeadlineNotPassed,
+    
+    #[msg("Match account must be closed first")]
+    MatchNotClosed,
+    
+    #[msg("Abandon timeout is outside the allowed range")]
+    InvalidAbandonTimeout,
+    
+    #[msg("Match cannot be abandoned yet")]
+    AbandonTooEarly,
+    
+    #[msg("Join deadline has not passed yet")]
+    JoinDeadlineNotPassed,
+    
+    #[msg("Remaining accounts must be (match, escrow, player A, receipt) quadruples")]
+    InvalidExpiryAccounts,
+    
+    #[msg("Join window bounds are invalid")]
+    InvalidJoinWindow,
+    
+    #[msg("This match is reserved for another player")]
+    NotInvited,
+    
+    #[msg("Force refund grace period is outside the allowed range")]
+    InvalidForceRefundGrace,
+    
+    #[msg("Winner can still claim the payout; force refund not allowed yet")]
+    ForceRefundTooEarly,
+    
+    #[msg("Abort requires both players' signatures, or one plus a referee attestation")]
+    AbortNotAuthorized,
+    
+    #[msg("Rematch needs each player's signature or signed consent, and a signature from anyone topping up")]
+    RematchConsentMissing,
+    
+    #[msg("Only the receipt owner can close it")]
+    NotReceiptOwner,
+    
+    #[msg("Escrow balance does not cover the match's stakes and bonds")]
+    EscrowBalanceMismatch,
+    
+    #[msg("Stake amount is outside the configured bounds")]
+    InvalidStakeAmount,
+    
+    #[msg("Invalid stake bounds: minimum must be at least the floor and not above the maximum")]
+    InvalidStakeBounds,
+    
+    #[msg("Token accounts are required for matches staked in a mint")]
+    MissingTokenAccounts,
+    
+    #[msg("Mint does not match the match or fee vault")]
+    InvalidMint,
+    
+    #[msg("Token account has the wrong program, mint or owner")]
+    InvalidTokenAccount,
+    
+    #[msg("Token escrow did not receive the expected amount")]
+    TokenAmountMismatch,
+    
+    #[msg("Fee schedule rates must be at most 10000 bps and the minimum fee not above the maximum")]
+    InvalidFeeSchedule,
+    
+    #[msg("Only the nominee can accept a role")]
+    NotPendingRole,
+    
+    #[msg("Only the fee withdrawer can withdraw fees")]
+    NotFeeWithdrawer,
+    
+    #[msg("Only the pauser can change the pause state")]
+    NotPauser,
+    
+    #[msg("Signer does not hold the referee role")]
+    NotReferee,
+    
+    #[msg("The fee withdrawer cannot also be the arbiter or referee")]
+    RoleConflict,
+    
+    #[msg("Only the holder of a role, or the admin, can nominate its successor")]
+    CannotNominateRole,
+    
+    #[msg("Fee withdrawals need multisig approval while the fee multisig is enabled")]
+    MultisigRequired,
+    
+    #[msg("Invalid fee multisig: duplicate signers, unreachable threshold or timings out of range")]
+    InvalidMultisig,
+    
+    #[msg("Signer is not a fee multisig signer")]
+    NotMultisigSigner,
+    
+    #[msg("Not enough current fee multisig signers co-signed this change")]
+    MultisigThresholdNotMet,
+    
+    #[msg("Signer already approved this withdrawal")]
+    AlreadyApproved,
+    
+    #[msg("Withdrawal has not reached the approval threshold")]
+    WithdrawalNotApproved,
+    
+    #[msg("Withdrawal delay has not elapsed")]
+    WithdrawalDelayNotElapsed,
+    
+    #[msg("Withdrawal proposal has expired")]
+    WithdrawalExpired,
+    
+    #[msg("Fee multisig changed since this withdrawal was proposed")]
+    MultisigChanged,
+    
+    #[msg("Recipient does not match the withdrawal proposal")]
+    InvalidRecipient,
+    
+    #[msg("This instruction is paused")]
+    Paused,
+    
+    #[msg("Unknown pause flags")]
+    InvalidPauseFlags,
+    
+    #[msg("Replay belongs to an earlier game of this match")]
+    StaleReplay,
+    
+    #[msg("Only the admin can perform this action")]
+    NotAdmin,
+    
+    #[msg("The admin and arbiter roles can only be rotated, never renounced")]
+    CannotRenounceRole,
+    
+    #[msg("Dispute timeout is outside the allowed range")]
+    InvalidDisputeTimeout,
+    
+    #[msg("The arbiter can still resolve this dispute")]
+    DisputeDeadlineNotPassed,
+    
+    #[msg("Dispute bond must be zero or at least the escrow's rent-exempt minimum")]
+    InvalidDisputeBond,
+    
+    #[msg("Someone already joined this match")]
+    MatchAlreadyJoined,
+    
+    #[msg("Only the match creator can perform this action")]
+    OnlyCreator,
+    
+    #[msg("Invalid Player A address")]
+    InvalidPlayerA,
+    
+    #[msg("Game account belongs to an earlier match")]
+    StaleGame,
+    
+    #[msg("Only correspondence matches are played on-chain")]
+    NotCorrespondenceMatch,
+    
+    #[msg("Referee abort attestations are only accepted shortly after the match starts")]
+    AbortAttestationExpired,
+    
+    #[msg("A move has already been played in this game")]
+    GameAlreadyStarted,
+    
+    #[msg("Expiry bounty exceeds the allowed maximum")]
+    InvalidExpiryBounty,
 }
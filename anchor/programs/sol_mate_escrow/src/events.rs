@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+/// Both stakes refunded in full because the players called the game off before it started.
+#[event]
+pub struct MatchAborted {
+    pub match_account: Pubkey,
+    pub player_a: Pubkey,
+    pub player_b: Pubkey,
+    pub refund_per_player: u64,
+    /// True if one player's signature was replaced by a referee "no moves yet" attestation
+    pub referee_attested: bool,
+}
+
+/// Both stakes refunded after the abandon timeout elapsed without a result.
+#[event]
+pub struct MatchAbandoned {
+    pub match_account: Pubkey,
+    pub player_a: Pubkey,
+    pub player_b: Pubkey,
+    pub refund_per_player: u64,
+    pub abandoned_by: Pubkey,
+}
+
+/// Both stakes and the dispute bond refunded because the arbiter never ruled.
+#[event]
+pub struct DisputeExpired {
+    pub match_account: Pubkey,
+    pub player_a: Pubkey,
+    pub player_b: Pubkey,
+    pub refund_per_player: u64,
+    pub disputer: Pubkey,
+    pub bond_refund: u64,
+}
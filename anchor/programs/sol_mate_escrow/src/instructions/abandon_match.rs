@@ -1,17 +1,22 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{transfer, Transfer};
 use crate::state::*;
 use crate::errors::*;
+use crate::payout::StakeVault;
+use crate::token::*;
+use crate::events::MatchAbandoned;
 
-/// Abandon an active match that has no winner.
-/// Either player can call this after a timeout period (e.g., 1 hour from match start).
-/// Both players get their stakes refunded.
+/// Abandon an active match that has no winner. Contested matches can only be
+/// settled through `open_dispute` / `resolve_dispute`.
+/// Either player can call this once the match's abandon timeout (configured per
+/// stake tier) has elapsed since player B joined. Both players get their stakes refunded.
+/// Correspondence matches are decided by `claim_win_on_time` instead.
 #[derive(Accounts)]
 pub struct AbandonMatch<'info> {
     #[account(
         mut,
         constraint = match_account.status == MatchStatus::Active @ EscrowError::MatchNotActive,
         constraint = match_account.winner.is_none() @ EscrowError::MatchAlreadyHasWinner,
+        constraint = !match_account.correspondence @ EscrowError::CorrespondenceMatch,
         close = player_a
     )]
     pub match_account: Account<'info, Match>,
@@ -24,10 +29,19 @@ pub struct AbandonMatch<'info> {
     /// CHECK: PDA for holding escrow funds
     pub escrow: AccountInfo<'info>,
     
+    #[account(
+        init,
+        payer = caller,
+        space = MatchReceipt::LEN,
+        seeds = [b"receipt", match_account.key().as_ref(), &match_account.nonce.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Account<'info, MatchReceipt>,
+    
     /// CHECK: Player A receives their stake back and rent from closed account
     #[account(
         mut,
-        constraint = player_a.key() == match_account.player_a
+        constraint = player_a.key() == match_account.player_a @ EscrowError::InvalidPlayerA
     )]
     pub player_a: AccountInfo<'info>,
     
@@ -40,56 +54,59 @@ pub struct AbandonMatch<'info> {
     
     /// The player calling this instruction (must be either player_a or player_b)
     #[account(
+        mut,
         constraint = caller.key() == match_account.player_a || 
                     (match_account.player_b.is_some() && caller.key() == match_account.player_b.unwrap()) 
                     @ EscrowError::NotAPlayer
     )]
     pub caller: Signer<'info>,
     
+    pub token: TokenAccounts<'info>,
+    
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<AbandonMatch>) -> Result<()> {
     let match_account = &ctx.accounts.match_account;
+    let clock = Clock::get()?;
+    
+    require!(
+        clock.unix_timestamp >= match_account.abandon_allowed_at(),
+        EscrowError::AbandonTooEarly
+    );
     
     // Get stake amount
     let stake_amount = match_account.stake_amount_lamports();
     
-    // Build escrow signer seeds
     let match_key = match_account.key();
-    let escrow_bump = match_account.escrow_bump;
-    let escrow_seeds: &[&[u8]] = &[
-        b"escrow",
-        match_key.as_ref(),
-        &[escrow_bump],
-    ];
-    
-    // Refund Player A
-    transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.escrow.to_account_info(),
-                to: ctx.accounts.player_a.to_account_info(),
-            },
-            &[escrow_seeds],
-        ),
-        stake_amount,
-    )?;
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let vault = StakeVault::new(match_account, &system_program, &ctx.accounts.escrow, &ctx.accounts.token)?;
+    
+    // Only settle against the expected escrow balance
+    vault.sweep_surplus(match_account, &ctx.accounts.player_a)?;
     
-    // Refund Player B
-    transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.escrow.to_account_info(),
-                to: ctx.accounts.player_b.to_account_info(),
-            },
-            &[escrow_seeds],
-        ),
-        stake_amount,
+    // Refund both players
+    vault.pay_player(&ctx.accounts.player_a, stake_amount)?;
+    vault.pay_player(&ctx.accounts.player_b, stake_amount)?;
+    vault.close(&ctx.accounts.player_a)?;
+    
+    ctx.accounts.receipt.record(
+        match_key,
+        match_account,
+        MatchOutcome::Abandoned,
+        [stake_amount, stake_amount],
+        0,
+        ctx.accounts.caller.key(),
     )?;
     
+    emit!(MatchAbandoned {
+        match_account: match_key,
+        player_a: ctx.accounts.player_a.key(),
+        player_b: ctx.accounts.player_b.key(),
+        refund_per_player: stake_amount,
+        abandoned_by: ctx.accounts.caller.key(),
+    });
+    
     msg!("Match abandoned. Stakes refunded to both players.");
     
     Ok(())
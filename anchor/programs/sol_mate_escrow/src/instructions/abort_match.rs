@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::MatchAborted;
+use crate::ed25519::require_signed_message;
+use crate::payout::StakeVault;
+use crate::token::*;
+
+/// Call off an active match before the first move, refunding both stakes in full
+/// with no fee. Needs both players' signatures, or one player's signature plus an
+/// ed25519 verification of the referee's signature over `Match::abort_message`,
+/// used before `Match::referee_abort_deadline`. Correspondence matches can only be
+/// aborted while no move is on the board.
+#[derive(Accounts)]
+pub struct AbortMatch<'info> {
+    #[account(
+        mut,
+        constraint = match_account.status == MatchStatus::Active @ EscrowError::MatchNotActive,
+        close = player_a
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow", match_account.key().as_ref()],
+        bump = match_account.escrow_bump
+    )]
+    /// CHECK: PDA for holding escrow funds
+    pub escrow: AccountInfo<'info>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = MatchReceipt::LEN,
+        seeds = [b"receipt", match_account.key().as_ref(), &match_account.nonce.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Account<'info, MatchReceipt>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// CHECK: Player A receives their stake back and rent from closed account; signature checked in handler
+    #[account(
+        mut,
+        constraint = player_a.key() == match_account.player_a @ EscrowError::InvalidPlayerA
+    )]
+    pub player_a: AccountInfo<'info>,
+    
+    /// CHECK: Player B receives their stake back; signature checked in handler
+    #[account(
+        mut,
+        constraint = match_account.player_b.is_some() && player_b.key() == match_account.player_b.unwrap() @ EscrowError::InvalidPlayerB
+    )]
+    pub player_b: AccountInfo<'info>,
+    
+    /// Pays rent for the receipt
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar, used to find the referee's ed25519 verification
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    /// Correspondence game of the match, closed along with it
+    #[account(
+        mut,
+        seeds = [b"game", match_account.key().as_ref()],
+        bump = game.bump,
+        close = player_a
+    )]
+    pub game: Option<Account<'info, CorrespondenceGame>>,
+    
+    pub token: TokenAccounts<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AbortMatch>) -> Result<()> {
+    // Correspondence games are closed along with their match
+    require!(
+        !ctx.accounts.match_account.correspondence || ctx.accounts.game.is_some(),
+        EscrowError::MissingGameAccount
+    );
+    
+    let match_account = &ctx.accounts.match_account;
+    let match_key = match_account.key();
+    
+    if match_account.correspondence {
+        let game = ctx.accounts.game.as_ref().ok_or(EscrowError::MissingGameAccount)?;
+        require!(game.ply == 0, EscrowError::GameAlreadyStarted);
+    }
+    
+    // Either both players agree, or one does and the referee attests nothing was played
+    let signers = [&ctx.accounts.player_a, &ctx.accounts.player_b]
+        .iter()
+        .filter(|player| player.is_signer)
+        .count();
+    let referee_attested = match signers {
+        2 => false,
+        1 => {
+            let referee = ctx.accounts.config.referee;
+            require!(referee != Pubkey::default(), EscrowError::AbortNotAuthorized);
+            require!(
+                Clock::get()?.unix_timestamp <= match_account.referee_abort_deadline(),
+                EscrowError::AbortAttestationExpired
+            );
+            let message = match_account.abort_message(&match_key);
+            require_signed_message(&ctx.accounts.instructions, &referee, &message)?;
+            true
+        }
+        _ => return err!(EscrowError::AbortNotAuthorized),
+    };
+    
+    let stake_amount = match_account.stake_amount_lamports();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let vault = StakeVault::new(match_account, &system_program, &ctx.accounts.escrow, &ctx.accounts.token)?;
+    
+    // Only settle against the expected escrow balance
+    vault.sweep_surplus(match_account, &ctx.accounts.player_a)?;
+    
+    for player in [&ctx.accounts.player_a, &ctx.accounts.player_b] {
+        vault.pay_player(player, stake_amount)?;
+    }
+    vault.close(&ctx.accounts.player_a)?;
+    
+    ctx.accounts.receipt.record(
+        match_key,
+        match_account,
+        MatchOutcome::Aborted,
+        [stake_amount, stake_amount],
+        0,
+        ctx.accounts.payer.key(),
+    )?;
+    
+    emit!(MatchAborted {
+        match_account: match_key,
+        player_a: ctx.accounts.player_a.key(),
+        player_b: ctx.accounts.player_b.key(),
+        refund_per_player: stake_amount,
+        referee_attested,
+    });
+    
+    msg!("Match aborted. Stakes refunded to both players in full.");
+    
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Role};
+use crate::errors::EscrowError;
+
+/// Second step of a role rotation: the nominee signs to take over.
+/// Requiring their signature means a role can never go to a key nobody controls.
+#[derive(Accounts)]
+pub struct AcceptRole<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub nominee: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptRole>, role: Role) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let nominee = ctx.accounts.nominee.key();
+    require!(
+        config.pending_roles[role as usize] == nominee,
+        EscrowError::NotPendingRole
+    );
+    require!(!config.role_conflicts(role, &nominee), EscrowError::RoleConflict);
+    
+    let old_holder = config.role_holder(role);
+    config.set_role_holder(role, nominee);
+    config.pending_roles[role as usize] = Pubkey::default();
+    
+    msg!("{:?} rotated from {} to {}", role, old_holder, nominee);
+    
+    Ok(())
+}
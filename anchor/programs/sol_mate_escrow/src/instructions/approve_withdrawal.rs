@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, WithdrawalProposal};
+use crate::errors::EscrowError;
+
+/// Approve a pending fee withdrawal as one of the fee multisig signers.
+#[derive(Accounts)]
+pub struct ApproveWithdrawal<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        seeds = [b"withdrawal".as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = proposal.multisig_version == config.fee_multisig.version @ EscrowError::MultisigChanged,
+    )]
+    pub proposal: Account<'info, WithdrawalProposal>,
+    
+    pub signer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ApproveWithdrawal>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let multisig = ctx.accounts.config.fee_multisig;
+    let index = multisig
+        .signer_index(&ctx.accounts.signer.key())
+        .ok_or(EscrowError::NotMultisigSigner)?;
+    
+    let proposal = &mut ctx.accounts.proposal;
+    require!(now < proposal.expires_at, EscrowError::WithdrawalExpired);
+    proposal.approve(index, multisig.threshold, now)?;
+    
+    msg!(
+        "Withdrawal {} approved by {} ({}/{})",
+        proposal.id,
+        ctx.accounts.signer.key(),
+        proposal.approval_count(),
+        multisig.threshold
+    );
+    
+    Ok(())
+}
@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{transfer, Transfer};
 use crate::state::*;
 use crate::errors::*;
+use crate::payout::StakeVault;
+use crate::token::*;
 
 #[derive(Accounts)]
 pub struct CancelMatch<'info> {
@@ -21,40 +22,66 @@ pub struct CancelMatch<'info> {
     /// CHECK: PDA for holding escrow funds
     pub escrow: AccountInfo<'info>,
     
+    #[account(
+        init,
+        payer = player_a,
+        space = MatchReceipt::LEN,
+        seeds = [b"receipt", match_account.key().as_ref(), &match_account.nonce.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Account<'info, MatchReceipt>,
+    
     #[account(
         mut,
         constraint = player_a.key() == match_account.player_a @ EscrowError::OnlyCreatorCanCancel
     )]
     pub player_a: Signer<'info>,
     
+    /// Correspondence game of the match, closed along with it
+    #[account(
+        mut,
+        seeds = [b"game", match_account.key().as_ref()],
+        bump = game.bump,
+        close = player_a
+    )]
+    pub game: Option<Account<'info, CorrespondenceGame>>,
+    
+    pub token: TokenAccounts<'info>,
+    
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<CancelMatch>) -> Result<()> {
+    // Correspondence games are closed along with their match
+    require!(
+        !ctx.accounts.match_account.correspondence || ctx.accounts.game.is_some(),
+        EscrowError::MissingGameAccount
+    );
+    
     let match_account = &ctx.accounts.match_account;
     
     // Get stake amount before we modify anything
     let stake_amount = match_account.stake_amount_lamports();
     
-    // Transfer stake back to player A using PDA signer seeds
+    // Transfer stake back to player A
     let match_key = match_account.key();
-    let escrow_bump = match_account.escrow_bump;
-    let escrow_seeds: &[&[u8]] = &[
-        b"escrow",
-        match_key.as_ref(),
-        &[escrow_bump],
-    ];
-    
-    transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.escrow.to_account_info(),
-                to: ctx.accounts.player_a.to_account_info(),
-            },
-            &[escrow_seeds],
-        ),
-        stake_amount,
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let player_a = ctx.accounts.player_a.to_account_info();
+    let vault = StakeVault::new(match_account, &system_program, &ctx.accounts.escrow, &ctx.accounts.token)?;
+    
+    // Only settle against the expected escrow balance
+    vault.sweep_surplus(match_account, &player_a)?;
+    
+    vault.pay_player(&player_a, stake_amount)?;
+    vault.close(&player_a)?;
+    
+    ctx.accounts.receipt.record(
+        match_key,
+        match_account,
+        MatchOutcome::Cancelled,
+        [stake_amount, 0],
+        0,
+        ctx.accounts.player_a.key(),
     )?;
     
     msg!("Match cancelled. Stake refunded to player A.");
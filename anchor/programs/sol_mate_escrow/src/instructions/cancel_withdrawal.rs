@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, WithdrawalProposal};
+use crate::errors::EscrowError;
+
+/// Cancel a fee withdrawal, closing the proposal to its proposer. Any multisig
+/// signer can cancel a live proposal; anyone can clean up one that has expired
+/// or was invalidated by a multisig change.
+#[derive(Accounts)]
+pub struct CancelWithdrawal<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        seeds = [b"withdrawal".as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        close = proposer
+    )]
+    pub proposal: Account<'info, WithdrawalProposal>,
+    
+    /// CHECK: Proposer receives the proposal account's rent
+    #[account(
+        mut,
+        constraint = proposer.key() == proposal.proposer
+    )]
+    pub proposer: AccountInfo<'info>,
+    
+    pub caller: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelWithdrawal>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let multisig = ctx.accounts.config.fee_multisig;
+    let proposal = &ctx.accounts.proposal;
+    
+    let stale = now >= proposal.expires_at || proposal.multisig_version != multisig.version;
+    require!(
+        stale || multisig.signer_index(&ctx.accounts.caller.key()).is_some(),
+        EscrowError::NotMultisigSigner
+    );
+    
+    msg!("Withdrawal {} cancelled by {}", proposal.id, ctx.accounts.caller.key());
+    
+    Ok(())
+}
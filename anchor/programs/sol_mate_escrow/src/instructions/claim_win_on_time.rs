@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Claim a correspondence match after the side to move missed their deadline.
+/// Only their opponent can claim; the match becomes Finished, ready for `confirm_payout`.
+#[derive(Accounts)]
+pub struct ClaimWinOnTime<'info> {
+    #[account(
+        mut,
+        constraint = match_account.status == MatchStatus::Active @ EscrowError::MatchNotActive,
+        constraint = match_account.correspondence @ EscrowError::NotCorrespondenceMatch,
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    #[account(
+        seeds = [b"game", match_account.key().as_ref()],
+        bump = game.bump,
+        constraint = game.belongs_to(&match_account) @ EscrowError::StaleGame
+    )]
+    pub game: Account<'info, CorrespondenceGame>,
+    
+    pub claimant: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ClaimWinOnTime>) -> Result<()> {
+    let match_account = &mut ctx.accounts.match_account;
+    let game = &ctx.accounts.game;
+    let clock = Clock::get()?;
+    
+    let waiting_on = game.board.position().side_to_move.opposite();
+    require!(
+        game.player(match_account, waiting_on) == Some(ctx.accounts.claimant.key()),
+        EscrowError::NotAPlayer
+    );
+    require!(
+        clock.unix_timestamp > game.move_deadline(),
+        EscrowError::MoveDeadlineNotPassed
+    );
+    
+    match_account.winner = Some(ctx.accounts.claimant.key());
+    match_account.status = MatchStatus::Finished;
+    match_account.finished_at = clock.unix_timestamp;
+    match_account.end_reason = Some(EndReason::Timeout);
+    
+    msg!("Win on time claimed at ply {}. Winner: {}", game.ply, ctx.accounts.claimant.key());
+    
+    Ok(())
+}
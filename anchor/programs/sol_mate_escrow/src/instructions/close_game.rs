@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Reclaim the rent of a correspondence game left behind after its match was closed.
+#[derive(Accounts)]
+pub struct CloseGame<'info> {
+    #[account(
+        mut,
+        seeds = [b"game", game.match_account.as_ref()],
+        bump = game.bump,
+        close = host
+    )]
+    pub game: Account<'info, CorrespondenceGame>,
+    
+    /// CHECK: Only checked to be closed
+    #[account(
+        constraint = match_account.key() == game.match_account,
+        constraint = match_account.data_is_empty() @ EscrowError::MatchNotClosed
+    )]
+    pub match_account: AccountInfo<'info>,
+    
+    /// Host who paid for the game account
+    #[account(
+        mut,
+        constraint = host.key() == game.host @ EscrowError::Unauthorized
+    )]
+    pub host: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseGame>) -> Result<()> {
+    msg!("Correspondence game {} closed", ctx.accounts.game.key());
+    
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Prune a match receipt, returning its rent to the owner who paid for it.
+#[derive(Accounts)]
+pub struct CloseReceipt<'info> {
+    #[account(
+        mut,
+        has_one = owner @ EscrowError::NotReceiptOwner,
+        close = owner
+    )]
+    pub receipt: Account<'info, MatchReceipt>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseReceipt>) -> Result<()> {
+    msg!(
+        "Receipt for game {} of match {} closed.",
+        ctx.accounts.receipt.game_index,
+        ctx.accounts.receipt.match_account
+    );
+    
+    Ok(())
+}
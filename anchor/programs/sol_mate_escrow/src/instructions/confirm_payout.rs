@@ -1,13 +1,16 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{transfer, Transfer};
 use crate::state::*;
 use crate::errors::*;
+use crate::payout::{record_stake_fee, winner_payout, StakeVault};
+use crate::token::*;
 
 #[derive(Accounts)]
 pub struct ConfirmPayout<'info> {
     #[account(
         mut,
-        constraint = match_account.status == MatchStatus::Finished @ EscrowError::MatchNotFinished,
+        constraint = match_account.status == MatchStatus::Finished
+                  || match_account.status == MatchStatus::ResultProposed
+                  @ EscrowError::MatchNotFinished,
         close = player_a
     )]
     pub match_account: Account<'info, Match>,
@@ -20,6 +23,15 @@ pub struct ConfirmPayout<'info> {
     /// CHECK: PDA for holding escrow funds
     pub escrow: AccountInfo<'info>,
     
+    #[account(
+        init,
+        payer = payer,
+        space = MatchReceipt::LEN,
+        seeds = [b"receipt", match_account.key().as_ref(), &match_account.nonce.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Account<'info, MatchReceipt>,
+    
     #[account(
         init_if_needed,
         payer = payer,
@@ -33,20 +45,50 @@ pub struct ConfirmPayout<'info> {
     /// CHECK: Winner receives payout
     pub winner: AccountInfo<'info>,
     
-    #[account(mut)]
-    /// CHECK: Player A created the match
+    /// CHECK: Player A created the match and receives rent from closed accounts
+    #[account(
+        mut,
+        constraint = player_a.key() == match_account.player_a @ EscrowError::InvalidPlayerA
+    )]
     pub player_a: AccountInfo<'info>,
     
     #[account(mut)]
     pub payer: Signer<'info>,
     
+    /// Correspondence game of the match, closed along with it
+    #[account(
+        mut,
+        seeds = [b"game", match_account.key().as_ref()],
+        bump = game.bump,
+        close = player_a
+    )]
+    pub game: Option<Account<'info, CorrespondenceGame>>,
+    
+    pub token: TokenAccounts<'info>,
+    
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<ConfirmPayout>) -> Result<()> {
-    let match_account = &ctx.accounts.match_account;
+    // Correspondence games are closed along with their match
+    require!(
+        !ctx.accounts.match_account.correspondence || ctx.accounts.game.is_some(),
+        EscrowError::MissingGameAccount
+    );
+    
+    let match_account = &mut ctx.accounts.match_account;
+    
+    // An unconfirmed proposal only becomes payable once nobody contested it in time
+    if match_account.status == MatchStatus::ResultProposed {
+        let clock = Clock::get()?;
+        require!(
+            !match_account.challenge_window_open(clock.unix_timestamp),
+            EscrowError::ChallengeWindowOpen
+        );
+        match_account.finished_at = clock.unix_timestamp;
+    }
     
-    // Verify winner
+    // Verify winner (proposed draws are settled with settle_draw)
     let winner_key = match_account.winner.ok_or(EscrowError::MatchNotFinished)?;
     require!(
         winner_key == ctx.accounts.winner.key(),
@@ -54,68 +96,48 @@ pub fn handler(ctx: Context<ConfirmPayout>) -> Result<()> {
     );
     
     // Calculate amounts
-    let stake_amount = match_account.stake_amount_lamports();
-    let total_pot = stake_amount
-        .checked_mul(2)
-        .ok_or(EscrowError::ArithmeticOverflow)?;
-    
-    // Calculate fee (10% from each player = 20% of one stake = 10% of total pot)
-    let fee_amount = total_pot
-        .checked_div(10)
-        .ok_or(EscrowError::ArithmeticOverflow)?;
-    
-    let payout_amount = total_pot
-        .checked_sub(fee_amount)
-        .ok_or(EscrowError::ArithmeticOverflow)?;
-    
-    msg!("Total pot: {} lamports", total_pot);
-    msg!("Fee (10%): {} lamports", fee_amount);
+    let (fee_amount, payout_amount) = winner_payout(match_account)?;
+    
+    msg!("Total pot: {} lamports", fee_amount + payout_amount);
+    msg!("Fee: {} lamports", fee_amount);
     msg!("Payout to winner: {} lamports", payout_amount);
     
-    // Create escrow signer seeds
-    let match_key = ctx.accounts.match_account.key();
-    let escrow_seeds = &[
-        b"escrow".as_ref(),
-        match_key.as_ref(),
-        &[ctx.accounts.match_account.escrow_bump],
-    ];
-    let escrow_signer = &[&escrow_seeds[..]];
-    
-    // Transfer fee to vault using CPI
-    transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.escrow.to_account_info(),
-                to: ctx.accounts.fee_vault.to_account_info(),
-            },
-            escrow_signer,
-        ),
+    let match_key = match_account.key();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let vault = StakeVault::new(match_account, &system_program, &ctx.accounts.escrow, &ctx.accounts.token)?;
+    
+    // Only settle against the expected escrow balance
+    vault.sweep_surplus(match_account, &ctx.accounts.player_a)?;
+    
+    // Transfer fee to vault
+    let fee_received = vault.pay_fee(&ctx.accounts.fee_vault.to_account_info(), fee_amount)?;
+    
+    // Transfer payout to winner
+    vault.pay_player(&ctx.accounts.winner, payout_amount)?;
+    vault.close(&ctx.accounts.player_a)?;
+    
+    let paid = if winner_key == match_account.player_a {
+        [payout_amount, 0]
+    } else {
+        [0, payout_amount]
+    };
+    ctx.accounts.receipt.record(
+        match_key,
+        match_account,
+        MatchOutcome::Won,
+        paid,
         fee_amount,
-    )?;
-    
-    // Transfer payout to winner using CPI
-    transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.escrow.to_account_info(),
-                to: ctx.accounts.winner.to_account_info(),
-            },
-            escrow_signer,
-        ),
-        payout_amount,
+        ctx.accounts.payer.key(),
     )?;
     
     // Update fee vault stats
-    let fee_vault = &mut ctx.accounts.fee_vault;
-    if fee_vault.total_collected == 0 {
-        fee_vault.bump = *ctx.bumps.get("fee_vault").unwrap();
-    }
-    fee_vault.total_collected = fee_vault
-        .total_collected
-        .checked_add(fee_amount)
-        .ok_or(EscrowError::ArithmeticOverflow)?;
+    record_stake_fee(
+        &mut ctx.accounts.fee_vault,
+        *ctx.bumps.get("fee_vault").unwrap(),
+        ctx.accounts.token.token_fee_vault.as_mut(),
+        match_account,
+        fee_received,
+    )?;
     
     msg!("Payout complete. Match account closed.");
     
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Accept the result proposed by the opponent, making it payable immediately.
+#[derive(Accounts)]
+pub struct ConfirmResult<'info> {
+    #[account(
+        mut,
+        constraint = match_account.status == MatchStatus::ResultProposed @ EscrowError::ResultNotProposed,
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    #[account(
+        constraint = match_account.proposed_by.is_some()
+                  && match_account.opponent_of(&confirmer.key()) == match_account.proposed_by
+                  @ EscrowError::CannotRespondToOwnProposal
+    )]
+    pub confirmer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ConfirmResult>) -> Result<()> {
+    let match_account = &mut ctx.accounts.match_account;
+    
+    if match_account.is_draw {
+        match_account.status = MatchStatus::Drawn;
+        match_account.finished_at = Clock::get()?.unix_timestamp;
+        msg!("Draw confirmed.");
+    } else {
+        match_account.status = MatchStatus::Finished;
+        match_account.finished_at = Clock::get()?.unix_timestamp;
+        msg!(
+            "Result confirmed. Winner: {}",
+            match_account.winner.ok_or(EscrowError::ResultNotProposed)?
+        );
+    }
+    
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Reject the result proposed by the opponent while the challenge window is open.
+/// The proposed winner is cleared so the match can no longer be paid out.
+/// A contested match can only be settled through a dispute, so contesting needs an
+/// arbiter to be configured.
+#[derive(Accounts)]
+pub struct ContestResult<'info> {
+    #[account(
+        mut,
+        constraint = match_account.status == MatchStatus::ResultProposed @ EscrowError::ResultNotProposed,
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.arbiter != Pubkey::default() @ EscrowError::ArbiterNotConfigured,
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        constraint = match_account.proposed_by.is_some()
+                  && match_account.opponent_of(&challenger.key()) == match_account.proposed_by
+                  @ EscrowError::CannotRespondToOwnProposal
+    )]
+    pub challenger: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ContestResult>) -> Result<()> {
+    let match_account = &mut ctx.accounts.match_account;
+    let clock = Clock::get()?;
+    
+    require!(
+        match_account.challenge_window_open(clock.unix_timestamp),
+        EscrowError::ChallengeWindowClosed
+    );
+    
+    if match_account.is_draw {
+        msg!("Draw contested by {}", ctx.accounts.challenger.key());
+    } else {
+        msg!(
+            "Result contested by {}. Proposed winner was: {}",
+            ctx.accounts.challenger.key(),
+            match_account.winner.ok_or(EscrowError::ResultNotProposed)?
+        );
+    }
+    
+    match_account.winner = None;
+    match_account.is_draw = false;
+    match_account.status = MatchStatus::Contested;
+    
+    Ok(())
+}
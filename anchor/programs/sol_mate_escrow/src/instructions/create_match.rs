@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
 use crate::state::*;
 use crate::errors::*;
+use crate::payout::StakeVault;
+use crate::token::*;
 
 #[derive(Accounts)]
 #[instruction(stake_tier: u8, seed: u64)]
@@ -27,9 +28,19 @@ pub struct CreateMatch<'info> {
     /// CHECK: PDA for holding escrow funds
     pub escrow: AccountInfo<'info>,
     
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(Config::PAUSE_CREATE) @ EscrowError::Paused,
+    )]
+    pub config: Account<'info, Config>,
+    
     #[account(mut)]
     pub player_a: Signer<'info>,
     
+    /// Set `mint` (with its fee vault and token accounts) to stake in a token instead of SOL
+    pub token: TokenAccounts<'info>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -38,12 +49,42 @@ pub fn handler(
     stake_tier: u8,
     seed: u64,
     join_deadline: i64,
+    stake_amount: u64,
 ) -> Result<()> {
     // Log the seed used for PDA derivation (this also silences unused warning)
     msg!("Creating match with seed: {}", seed);
     
-    // Validate stake tier (0-3 are normal tiers, 4 is test tier)
-    require!(stake_tier <= 4, EscrowError::InvalidStakeTier);
+    // Validate stake tier against the enabled entries of the config's tier table
+    let tier = ctx.accounts.config.stake_tier(stake_tier).ok_or(EscrowError::InvalidStakeTier)?;
+    
+    // Token stakes take their bounds from the mint's fee vault, which also whitelists the mint
+    let mint = match &ctx.accounts.token.mint {
+        Some(mint) => {
+            let token_fee_vault = ctx.accounts.token.token_fee_vault.as_ref().ok_or(EscrowError::MissingTokenAccounts)?;
+            require!(token_fee_vault.mint == mint.key(), EscrowError::InvalidMint);
+            require!(token_fee_vault.stake_valid(stake_amount), EscrowError::InvalidStakeAmount);
+            mint.key()
+        }
+        None => Pubkey::default(),
+    };
+    
+    // A zero stake amount takes the tier's preset; either way the amount is snapshotted
+    // into the match, so re-pricing the tier later does not affect it
+    let stake_amount = if stake_amount == 0 && mint == Pubkey::default() {
+        tier.lamports
+    } else {
+        stake_amount
+    };
+    if mint == Pubkey::default() {
+        require!(ctx.accounts.config.stake_valid(stake_amount), EscrowError::InvalidStakeAmount);
+    }
+    
+    // Validate join deadline is neither in the past nor too far out
+    let clock = Clock::get()?;
+    require!(
+        ctx.accounts.config.join_deadline_valid(join_deadline, clock.unix_timestamp),
+        EscrowError::InvalidTimestamp
+    );
     
     let match_account = &mut ctx.accounts.match_account;
     
@@ -56,25 +97,60 @@ pub fn handler(
     match_account.winner = None;
     match_account.bump = *ctx.bumps.get("match_account").unwrap();
     match_account.escrow_bump = *ctx.bumps.get("escrow").unwrap();
+    match_account.proposed_by = None;
+    match_account.proposed_at = 0;
+    match_account.challenge_window = ctx.accounts.config.challenge_window;
+    match_account.nonce = Match::first_nonce(clock.slot);
+    match_account.disputed_by = None;
+    match_account.dispute_bond = 0;
+    match_account.dispute_deadline = 0;
+    match_account.is_draw = false;
+    match_account.draw_fee_bps = ctx.accounts.config.draw_fee_bps;
+    match_account.correspondence = false;
+    match_account.started_at = 0;
+    match_account.abandon_timeout = ctx.accounts.config.abandon_timeout(stake_tier);
+    match_account.invitee = None;
+    match_account.finished_at = 0;
+    match_account.game_index = 0;
+    match_account.player_a_white = true;
+    match_account.end_reason = None;
+    match_account.stake_amount = stake_amount;
+    match_account.mint = mint;
+    match_account.token_escrow_bump = 0;
+    match_account.snapshot_fees(&ctx.accounts.config.fee_schedule);
     
-    // Calculate stake amount
-    let stake_amount = match_account.stake_amount_lamports();
+    let match_key = match_account.key();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let player_a = ctx.accounts.player_a.to_account_info();
     
-    // Transfer player A's stake to escrow
-    let transfer_ix = system_program::Transfer {
-        from: ctx.accounts.player_a.to_account_info(),
-        to: ctx.accounts.escrow.to_account_info(),
-    };
+    // Token stakes are held in a token account owned by the escrow PDA
+    if match_account.is_token_match() {
+        let token = &ctx.accounts.token;
+        let token_program = token.token_program.as_deref().ok_or(EscrowError::MissingTokenAccounts)?;
+        let token_escrow = token.token_escrow.as_deref().ok_or(EscrowError::MissingTokenAccounts)?;
+        let mint = token.mint.as_deref().ok_or(EscrowError::MissingTokenAccounts)?;
+        check_token_program(token_program)?;
+        
+        let (expected_token_escrow, token_escrow_bump) =
+            Pubkey::find_program_address(&[b"token_escrow", match_key.as_ref()], ctx.program_id);
+        require!(token_escrow.key() == expected_token_escrow, EscrowError::InvalidTokenAccount);
+        create_token_account(
+            &player_a,
+            token_escrow,
+            &[b"token_escrow", match_key.as_ref(), &[token_escrow_bump]],
+            mint,
+            ctx.accounts.escrow.key,
+            token_program,
+            &system_program,
+        )?;
+        match_account.token_escrow_bump = token_escrow_bump;
+    }
     
-    system_program::transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            transfer_ix,
-        ),
-        stake_amount,
-    )?;
+    // Transfer player A's stake to escrow
+    let vault = StakeVault::new(match_account, &system_program, &ctx.accounts.escrow, &ctx.accounts.token)?;
+    vault.deposit(&player_a, stake_amount)?;
     
-    msg!("Match created with stake tier: {} ({} lamports)", stake_tier, stake_amount);
+    msg!("Match created with stake tier: {} ({} of mint {})", stake_tier, stake_amount, mint);
     msg!("Join deadline: {}", join_deadline);
     
     Ok(())
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::chess::Position;
+
+/// Turn an open match into a correspondence match, where every move is played
+/// on-chain with `make_move` and the result is decided by the board.
+/// Only the host can do this, and only before anyone joins.
+#[derive(Accounts)]
+pub struct EnableCorrespondence<'info> {
+    #[account(
+        mut,
+        constraint = match_account.status == MatchStatus::Open @ EscrowError::MatchNotOpen,
+        constraint = match_account.player_b.is_none() @ EscrowError::MatchAlreadyJoined,
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(Config::PAUSE_CREATE) @ EscrowError::Paused,
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// Created, or reset if an earlier match at this address left one behind
+    #[account(
+        init_if_needed,
+        payer = player_a,
+        space = CorrespondenceGame::LEN,
+        seeds = [b"game", match_account.key().as_ref()],
+        bump
+    )]
+    pub game: Account<'info, CorrespondenceGame>,
+    
+    #[account(
+        mut,
+        constraint = player_a.key() == match_account.player_a @ EscrowError::OnlyCreator
+    )]
+    pub player_a: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<EnableCorrespondence>,
+    seconds_per_move: i64,
+    host_plays_white: bool,
+) -> Result<()> {
+    require!(
+        (CorrespondenceGame::MIN_SECONDS_PER_MOVE..=CorrespondenceGame::MAX_SECONDS_PER_MOVE)
+            .contains(&seconds_per_move),
+        EscrowError::InvalidMoveTime
+    );
+    
+    let game = &mut ctx.accounts.game;
+    game.match_account = ctx.accounts.match_account.key();
+    game.host = ctx.accounts.player_a.key();
+    game.host_plays_white = host_plays_white;
+    game.board.set_position(&Position::start());
+    game.ply = 0;
+    game.seconds_per_move = seconds_per_move;
+    game.turn_started_at = 0;
+    game.bump = *ctx.bumps.get("game").unwrap();
+    game.nonce = ctx.accounts.match_account.nonce;
+    
+    ctx.accounts.match_account.correspondence = true;
+    ctx.accounts.match_account.player_a_white = host_plays_white;
+    
+    msg!("Correspondence mode enabled: {}s per move", seconds_per_move);
+    msg!("Host plays {}", if host_plays_white { "white" } else { "black" });
+    
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, FeeVault, TokenFeeVault, WithdrawalProposal};
+use crate::errors::EscrowError;
+use crate::payout::{transfer_sol_fees, transfer_token_fees};
+use crate::token::token_balance;
+
+/// Carry out a fee withdrawal once it has reached the multisig threshold and the
+/// configured delay has passed. Any multisig signer can execute it; the proposal
+/// is closed to its proposer.
+#[derive(Accounts)]
+pub struct ExecuteWithdrawal<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        seeds = [b"withdrawal".as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = proposal.multisig_version == config.fee_multisig.version @ EscrowError::MultisigChanged,
+        close = proposer
+    )]
+    pub proposal: Account<'info, WithdrawalProposal>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump = fee_vault.bump,
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    /// CHECK: Receives SOL withdrawals, checked against the proposal
+    #[account(
+        mut,
+        constraint = recipient.key() == proposal.recipient @ EscrowError::InvalidRecipient
+    )]
+    pub recipient: AccountInfo<'info>,
+    
+    /// CHECK: Proposer receives the proposal account's rent
+    #[account(
+        mut,
+        constraint = proposer.key() == proposal.proposer
+    )]
+    pub proposer: AccountInfo<'info>,
+    
+    pub executor: Signer<'info>,
+    
+    /// Fee vault of the proposal's mint, for token withdrawals
+    #[account(
+        seeds = [b"fee_vault", proposal.mint.as_ref()],
+        bump = token_fee_vault.bump,
+    )]
+    pub token_fee_vault: Option<Account<'info, TokenFeeVault>>,
+    
+    /// CHECK: Token account of the mint's fee vault, checked by seeds
+    #[account(
+        mut,
+        seeds = [b"fee_vault_token", proposal.mint.as_ref()],
+        bump,
+    )]
+    pub fee_vault_token: Option<AccountInfo<'info>>,
+    
+    /// CHECK: Proposal's mint, checked against the token fee vault
+    pub mint: Option<AccountInfo<'info>>,
+    
+    /// CHECK: Recipient's token account of the mint, checked in handler
+    #[account(mut)]
+    pub destination: Option<AccountInfo<'info>>,
+    
+    /// CHECK: SPL Token or Token-2022 program, checked when transferring
+    pub token_program: Option<AccountInfo<'info>>,
+}
+
+pub fn handler(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let multisig = ctx.accounts.config.fee_multisig;
+    require!(
+        multisig.signer_index(&ctx.accounts.executor.key()).is_some(),
+        EscrowError::NotMultisigSigner
+    );
+    
+    let proposal = &ctx.accounts.proposal;
+    require!(now < proposal.expires_at, EscrowError::WithdrawalExpired);
+    require!(proposal.approved_at > 0, EscrowError::WithdrawalNotApproved);
+    let executable_at = proposal
+        .approved_at
+        .checked_add(multisig.delay)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    require!(now >= executable_at, EscrowError::WithdrawalDelayNotElapsed);
+    
+    if proposal.mint == Pubkey::default() {
+        transfer_sol_fees(
+            &ctx.accounts.fee_vault.to_account_info(),
+            &ctx.accounts.recipient,
+            proposal.amount,
+        )?;
+    } else {
+        let token_fee_vault = ctx.accounts.token_fee_vault.as_ref().ok_or(EscrowError::MissingTokenAccounts)?;
+        let fee_vault_token = ctx.accounts.fee_vault_token.as_ref().ok_or(EscrowError::MissingTokenAccounts)?;
+        let mint = ctx.accounts.mint.as_ref().ok_or(EscrowError::MissingTokenAccounts)?;
+        let destination = ctx.accounts.destination.as_ref().ok_or(EscrowError::MissingTokenAccounts)?;
+        let token_program = ctx.accounts.token_program.as_ref().ok_or(EscrowError::MissingTokenAccounts)?;
+        
+        // Tokens only go to a token account of the approved recipient
+        token_balance(destination, token_program, &proposal.mint, &proposal.recipient)?;
+        transfer_token_fees(
+            token_fee_vault,
+            fee_vault_token,
+            mint,
+            destination,
+            token_program,
+            proposal.amount,
+        )?;
+    }
+    
+    msg!("Withdrawal {} executed by {}", proposal.id, ctx.accounts.executor.key());
+    
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::DisputeExpired;
+use crate::payout::{transfer_from_escrow, StakeVault};
+use crate::token::*;
+
+/// Refund a disputed match the arbiter never ruled on.
+/// Anyone can call this once the match's dispute deadline has passed. Both stakes
+/// are returned in full, the disputer gets their bond back, and no fee is taken.
+#[derive(Accounts)]
+pub struct ExpireDispute<'info> {
+    #[account(
+        mut,
+        constraint = match_account.status == MatchStatus::Disputed @ EscrowError::MatchNotDisputed,
+        close = player_a
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow", match_account.key().as_ref()],
+        bump = match_account.escrow_bump
+    )]
+    /// CHECK: PDA for holding escrow funds
+    pub escrow: AccountInfo<'info>,
+    
+    #[account(
+        init,
+        payer = caller,
+        space = MatchReceipt::LEN,
+        seeds = [b"receipt", match_account.key().as_ref(), &match_account.nonce.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Account<'info, MatchReceipt>,
+    
+    /// CHECK: Player A receives their stake back and rent from closed account
+    #[account(
+        mut,
+        constraint = player_a.key() == match_account.player_a @ EscrowError::InvalidPlayerA
+    )]
+    pub player_a: AccountInfo<'info>,
+    
+    /// CHECK: Player B receives their stake back
+    #[account(
+        mut,
+        constraint = match_account.player_b.is_some() && player_b.key() == match_account.player_b.unwrap() @ EscrowError::InvalidPlayerB
+    )]
+    pub player_b: AccountInfo<'info>,
+    
+    /// Pays rent for the receipt
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    
+    pub token: TokenAccounts<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ExpireDispute>) -> Result<()> {
+    let match_account = &ctx.accounts.match_account;
+    let clock = Clock::get()?;
+    
+    require!(
+        clock.unix_timestamp >= match_account.dispute_deadline,
+        EscrowError::DisputeDeadlineNotPassed
+    );
+    
+    let disputer = match_account.disputed_by.ok_or(EscrowError::MatchNotDisputed)?;
+    let stake_amount = match_account.stake_amount_lamports();
+    let bond = match_account.dispute_bond;
+    
+    let match_key = match_account.key();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let vault = StakeVault::new(match_account, &system_program, &ctx.accounts.escrow, &ctx.accounts.token)?;
+    
+    // Only settle against the expected escrow balance
+    vault.sweep_surplus(match_account, &ctx.accounts.player_a)?;
+    
+    for player in [&ctx.accounts.player_a, &ctx.accounts.player_b] {
+        vault.pay_player(player, stake_amount)?;
+    }
+    vault.close(&ctx.accounts.player_a)?;
+    
+    // Dispute bonds are always held in lamports
+    if bond > 0 {
+        let disputer_info = if disputer == match_account.player_a {
+            &ctx.accounts.player_a
+        } else {
+            &ctx.accounts.player_b
+        };
+        transfer_from_escrow(
+            &system_program,
+            &ctx.accounts.escrow,
+            disputer_info,
+            &match_key,
+            match_account.escrow_bump,
+            bond,
+        )?;
+    }
+    
+    ctx.accounts.receipt.record(
+        match_key,
+        match_account,
+        MatchOutcome::Unresolved,
+        [stake_amount, stake_amount],
+        0,
+        ctx.accounts.caller.key(),
+    )?;
+    
+    emit!(DisputeExpired {
+        match_account: match_key,
+        player_a: ctx.accounts.player_a.key(),
+        player_b: ctx.accounts.player_b.key(),
+        refund_per_player: stake_amount,
+        disputer,
+        bond_refund: bond,
+    });
+    
+    msg!("Dispute expired without a ruling. Stakes and bond refunded.");
+    
+    Ok(())
+}
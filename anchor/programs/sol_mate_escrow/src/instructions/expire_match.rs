@@ -0,0 +1,252 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::AccountsClose;
+use crate::state::*;
+use crate::errors::*;
+use crate::payout::StakeVault;
+use crate::token::*;
+
+/// Expire an Open match whose join deadline has passed. Anyone can call this.
+/// Player A's stake is refunded and the match account closed to player A, except
+/// for the configured bounty which is paid to the caller out of the account's rent.
+/// Like every settlement, each expired match gets a receipt, paid for by the caller.
+///
+/// More matches can be expired in the same instruction by passing
+/// `(match, escrow, player_a, receipt)` quadruples as remaining accounts, all writable,
+/// where `receipt` is the match's uninitialized receipt PDA.
+/// Batched matches must be SOL matches without a correspondence game; token and
+/// correspondence matches are expired one at a time.
+#[derive(Accounts)]
+pub struct ExpireMatch<'info> {
+    #[account(mut)]
+    pub match_account: Account<'info, Match>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow", match_account.key().as_ref()],
+        bump = match_account.escrow_bump
+    )]
+    /// CHECK: PDA for holding escrow funds
+    pub escrow: AccountInfo<'info>,
+    
+    #[account(
+        init,
+        payer = caller,
+        space = MatchReceipt::LEN,
+        seeds = [b"receipt", match_account.key().as_ref(), &match_account.nonce.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Account<'info, MatchReceipt>,
+    
+    /// CHECK: Player A receives their stake back and rent from closed account
+    #[account(
+        mut,
+        constraint = player_a.key() == match_account.player_a @ EscrowError::InvalidPlayerA
+    )]
+    pub player_a: AccountInfo<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    
+    /// Correspondence game of the match, closed along with it
+    #[account(
+        mut,
+        seeds = [b"game", match_account.key().as_ref()],
+        bump = game.bump,
+        close = player_a
+    )]
+    pub game: Option<Account<'info, CorrespondenceGame>>,
+    
+    pub token: TokenAccounts<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts shared by every match expired in one instruction.
+struct Crank<'a, 'info> {
+    caller: &'a AccountInfo<'info>,
+    system_program: &'a AccountInfo<'info>,
+    token: &'a TokenAccounts<'info>,
+    bounty: u64,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ExpireMatch<'info>>) -> Result<()> {
+    // Correspondence games are closed along with their match
+    require!(
+        !ctx.accounts.match_account.correspondence || ctx.accounts.game.is_some(),
+        EscrowError::MissingGameAccount
+    );
+    
+    let caller = ctx.accounts.caller.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let crank = Crank {
+        caller: &caller,
+        system_program: &system_program,
+        token: &ctx.accounts.token,
+        bounty: ctx.accounts.config.expiry_bounty,
+    };
+    
+    crank.expire(
+        &ctx.accounts.match_account,
+        &ctx.accounts.escrow,
+        &ctx.accounts.player_a,
+        &mut ctx.accounts.receipt,
+    )?;
+    
+    let batch = ctx.remaining_accounts.chunks_exact(4);
+    require!(batch.remainder().is_empty(), EscrowError::InvalidExpiryAccounts);
+    
+    for accounts in batch {
+        let [match_info, escrow, player_a, receipt_info] = accounts else {
+            return err!(EscrowError::InvalidExpiryAccounts);
+        };
+        require!(
+            match_info.is_writable && escrow.is_writable && player_a.is_writable && receipt_info.is_writable,
+            EscrowError::InvalidExpiryAccounts
+        );
+        
+        let match_account: Account<'info, Match> = Account::try_from(match_info)?;
+        let expected_escrow = Pubkey::create_program_address(
+            &[b"escrow", match_info.key.as_ref(), &[match_account.escrow_bump]],
+            ctx.program_id,
+        )
+        .map_err(|_| EscrowError::InvalidExpiryAccounts)?;
+        require!(
+            escrow.key() == expected_escrow
+                && player_a.key() == match_account.player_a
+                && !match_account.is_token_match()
+                && !match_account.correspondence,
+            EscrowError::InvalidExpiryAccounts
+        );
+        
+        let mut receipt = crank.init_receipt(&match_account, receipt_info, ctx.program_id)?;
+        crank.expire(&match_account, escrow, player_a, &mut receipt)?;
+        receipt.exit(ctx.program_id)?;
+    }
+    
+    Ok(())
+}
+
+impl<'a, 'info> Crank<'a, 'info> {
+    /// Refund, record and close a single expired match.
+    fn expire(
+        &self,
+        match_account: &Account<'info, Match>,
+        escrow: &AccountInfo<'info>,
+        player_a: &AccountInfo<'info>,
+        receipt: &mut Account<'info, MatchReceipt>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(match_account.status == MatchStatus::Open, EscrowError::MatchNotOpen);
+        require!(match_account.player_b.is_none(), EscrowError::CannotCancelAfterJoin);
+        require!(now > match_account.join_deadline, EscrowError::JoinDeadlineNotPassed);
+        
+        let match_key = match_account.key();
+        let stake_amount = match_account.stake_amount_lamports();
+        
+        let vault = StakeVault::new(match_account, self.system_program, escrow, self.token)?;
+        vault.sweep_surplus(match_account, player_a)?;
+        vault.pay_player(player_a, stake_amount)?;
+        vault.close(player_a)?;
+        
+        receipt.record(
+            match_key,
+            match_account,
+            MatchOutcome::Expired,
+            [stake_amount, 0],
+            0,
+            self.caller.key(),
+        )?;
+        
+        // Pay the bounty straight out of the program-owned match account's rent
+        let match_info = match_account.to_account_info();
+        let bounty = self.bounty.min(match_info.lamports());
+        if bounty > 0 {
+            **match_info.try_borrow_mut_lamports()? -= bounty;
+            **self.caller.try_borrow_mut_lamports()? += bounty;
+        }
+        
+        match_account.close(player_a.clone())?;
+        
+        msg!("Match {} expired. Stake refunded to player A, bounty: {} lamports", match_key, bounty);
+        
+        Ok(())
+    }
+    
+    /// Create the receipt PDA of a batched match, as `init` does for the first one.
+    fn init_receipt(
+        &self,
+        match_account: &Account<'info, Match>,
+        receipt: &'a AccountInfo<'info>,
+        program_id: &Pubkey,
+    ) -> Result<Account<'info, MatchReceipt>> {
+        let match_key = match_account.key();
+        let nonce = match_account.nonce.to_le_bytes();
+        let (expected_receipt, bump) =
+            Pubkey::find_program_address(&[b"receipt", match_key.as_ref(), &nonce], program_id);
+        require!(receipt.key() == expected_receipt, EscrowError::InvalidExpiryAccounts);
+        let signer_seeds: &[&[u8]] = &[b"receipt", match_key.as_ref(), &nonce, &[bump]];
+        
+        // Top up a pre-funded address instead of failing to create it
+        let rent = Rent::get()?.minimum_balance(MatchReceipt::LEN);
+        let current_lamports = receipt.lamports();
+        if current_lamports == 0 {
+            system_program::create_account(
+                CpiContext::new_with_signer(
+                    self.system_program.clone(),
+                    system_program::CreateAccount {
+                        from: self.caller.clone(),
+                        to: receipt.clone(),
+                    },
+                    &[signer_seeds],
+                ),
+                rent,
+                MatchReceipt::LEN as u64,
+                program_id,
+            )?;
+        } else {
+            let top_up = rent.saturating_sub(current_lamports);
+            if top_up > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        self.system_program.clone(),
+                        system_program::Transfer {
+                            from: self.caller.clone(),
+                            to: receipt.clone(),
+                        },
+                    ),
+                    top_up,
+                )?;
+            }
+            system_program::allocate(
+                CpiContext::new_with_signer(
+                    self.system_program.clone(),
+                    system_program::Allocate {
+                        account_to_allocate: receipt.clone(),
+                    },
+                    &[signer_seeds],
+                ),
+                MatchReceipt::LEN as u64,
+            )?;
+            system_program::assign(
+                CpiContext::new_with_signer(
+                    self.system_program.clone(),
+                    system_program::Assign {
+                        account_to_assign: receipt.clone(),
+                    },
+                    &[signer_seeds],
+                ),
+                program_id,
+            )?;
+        }
+        
+        // The fresh account is zeroed, which decodes as an empty receipt
+        Account::try_from_unchecked(receipt)
+    }
+}
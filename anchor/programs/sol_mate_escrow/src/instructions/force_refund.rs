@@ -1,10 +1,13 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{transfer, Transfer};
 use crate::state::*;
 use crate::errors::*;
+use crate::payout::StakeVault;
+use crate::token::*;
 
 /// Force refund from a Finished match where payout failed.
-/// Either player can call this to recover funds.
+/// Either player can call this to recover funds, but only once the configured
+/// grace period since the match finished has passed without a payout; until
+/// then the winner can always claim through `confirm_payout`.
 /// Both players get their stakes refunded (no winner payout).
 #[derive(Accounts)]
 pub struct ForceRefund<'info> {
@@ -23,10 +26,25 @@ pub struct ForceRefund<'info> {
     /// CHECK: PDA for holding escrow funds
     pub escrow: AccountInfo<'info>,
     
+    #[account(
+        init,
+        payer = caller,
+        space = MatchReceipt::LEN,
+        seeds = [b"receipt", match_account.key().as_ref(), &match_account.nonce.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Account<'info, MatchReceipt>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
     /// CHECK: Player A receives their stake back and rent from closed account
     #[account(
         mut,
-        constraint = player_a.key() == match_account.player_a
+        constraint = player_a.key() == match_account.player_a @ EscrowError::InvalidPlayerA
     )]
     pub player_a: AccountInfo<'info>,
     
@@ -39,70 +57,71 @@ pub struct ForceRefund<'info> {
     
     /// The player calling this instruction (must be either player_a or player_b)
     #[account(
+        mut,
         constraint = caller.key() == match_account.player_a || 
                     (match_account.player_b.is_some() && caller.key() == match_account.player_b.unwrap()) 
                     @ EscrowError::NotAPlayer
     )]
     pub caller: Signer<'info>,
     
+    /// Correspondence game of the match, closed along with it
+    #[account(
+        mut,
+        seeds = [b"game", match_account.key().as_ref()],
+        bump = game.bump,
+        close = player_a
+    )]
+    pub game: Option<Account<'info, CorrespondenceGame>>,
+    
+    pub token: TokenAccounts<'info>,
+    
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<ForceRefund>) -> Result<()> {
+    // Correspondence games are closed along with their match
+    require!(
+        !ctx.accounts.match_account.correspondence || ctx.accounts.game.is_some(),
+        EscrowError::MissingGameAccount
+    );
+    
     let match_account = &ctx.accounts.match_account;
+    let clock = Clock::get()?;
+    
+    // The declared winner gets the grace period to claim before anyone can undo the result
+    require!(
+        clock.unix_timestamp
+            >= match_account.finished_at.saturating_add(ctx.accounts.config.force_refund_grace),
+        EscrowError::ForceRefundTooEarly
+    );
     
     // Get stake amount
     let stake_amount = match_account.stake_amount_lamports();
     
-    // Check escrow balance - might have partial funds
-    let escrow_balance = ctx.accounts.escrow.lamports();
-    
     msg!("Force refunding from stuck Finished match");
-    msg!("Escrow balance: {} lamports", escrow_balance);
-    msg!("Expected per player: {} lamports", stake_amount);
+    msg!("Refund per player: {}", stake_amount);
     
-    // Build escrow signer seeds
     let match_key = match_account.key();
-    let escrow_bump = match_account.escrow_bump;
-    let escrow_seeds: &[&[u8]] = &[
-        b"escrow",
-        match_key.as_ref(),
-        &[escrow_bump],
-    ];
-    
-    // Calculate how much each player gets (split whatever is in escrow)
-    let per_player = escrow_balance / 2;
-    let remainder = escrow_balance - (per_player * 2);
-    
-    // Refund Player A (gets remainder if any)
-    if per_player + remainder > 0 {
-        transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.escrow.to_account_info(),
-                    to: ctx.accounts.player_a.to_account_info(),
-                },
-                &[escrow_seeds],
-            ),
-            per_player + remainder,
-        )?;
-    }
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let vault = StakeVault::new(match_account, &system_program, &ctx.accounts.escrow, &ctx.accounts.token)?;
     
-    // Refund Player B
-    if per_player > 0 {
-        transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.escrow.to_account_info(),
-                    to: ctx.accounts.player_b.to_account_info(),
-                },
-                &[escrow_seeds],
-            ),
-            per_player,
-        )?;
+    // Only settle against the expected escrow balance
+    vault.sweep_surplus(match_account, &ctx.accounts.player_a)?;
+    
+    // Refund both players their stakes
+    for player in [&ctx.accounts.player_a, &ctx.accounts.player_b] {
+        vault.pay_player(player, stake_amount)?;
     }
+    vault.close(&ctx.accounts.player_a)?;
+    
+    ctx.accounts.receipt.record(
+        match_key,
+        match_account,
+        MatchOutcome::Refunded,
+        [stake_amount, stake_amount],
+        0,
+        ctx.accounts.caller.key(),
+    )?;
     
     msg!("Force refund complete. Both players refunded.");
     
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, FeeSchedule, Role, StakeTier};
+use crate::errors::EscrowError;
+use crate::program::SolMateEscrow;
+
+/// Create the global program config with default parameters.
+/// Only the program's upgrade authority can call this, once; it becomes the
+/// config admin, which can then be rotated with `propose_role` / `accept_role`.
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = Config::LEN,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, SolMateEscrow>,
+    
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ EscrowError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeConfig>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.admin.key();
+    config.fee_withdrawer = ctx.accounts.admin.key();
+    config.pauser = ctx.accounts.admin.key();
+    config.pending_roles = [Pubkey::default(); Role::COUNT];
+    config.paused = 0;
+    config.challenge_window = Config::DEFAULT_CHALLENGE_WINDOW;
+    config.referee = Pubkey::default();
+    config.arbiter = Pubkey::default();
+    config.dispute_bond = Config::DEFAULT_DISPUTE_BOND;
+    config.dispute_timeout = Config::DEFAULT_DISPUTE_TIMEOUT;
+    config.draw_fee_bps = Config::DEFAULT_DRAW_FEE_BPS;
+    config.abandon_timeouts = [Config::DEFAULT_ABANDON_TIMEOUT; Config::MAX_STAKE_TIERS];
+    config.expiry_bounty = 0;
+    config.min_join_window = Config::DEFAULT_MIN_JOIN_WINDOW;
+    config.max_join_window = Config::DEFAULT_MAX_JOIN_WINDOW;
+    config.force_refund_grace = Config::DEFAULT_FORCE_REFUND_GRACE;
+    config.min_stake = Config::DEFAULT_MIN_STAKE;
+    config.max_stake = Config::DEFAULT_MAX_STAKE;
+    config.bump = *ctx.bumps.get("config").unwrap();
+    config.stake_tiers = [StakeTier::default(); Config::MAX_STAKE_TIERS];
+    for (index, lamports) in Config::DEFAULT_STAKE_TIERS.into_iter().enumerate() {
+        let enabled = Config::tier_allowed(index as u8);
+        config.stake_tiers[index] = StakeTier { lamports, enabled };
+    }
+    config.fee_schedule = FeeSchedule {
+        maker_bps: [Config::DEFAULT_FEE_BPS; Config::MAX_STAKE_TIERS],
+        taker_bps: [Config::DEFAULT_FEE_BPS; Config::MAX_STAKE_TIERS],
+        min_fee: 0,
+        max_fee: 0,
+    };
+    
+    msg!("Config initialized. Challenge window: {}s", config.challenge_window);
+    
+    Ok(())
+}
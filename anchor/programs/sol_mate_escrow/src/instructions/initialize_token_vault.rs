@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Role, TokenFeeVault};
+use crate::errors::EscrowError;
+use crate::token::{check_token_program, create_token_account};
+
+/// Whitelist a mint for token stakes by creating its fee vault and the token
+/// account that collects its platform fees. Only the platform admin can call this.
+#[derive(Accounts)]
+pub struct InitializeTokenVault<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = TokenFeeVault::LEN,
+        seeds = [b"fee_vault", mint.key().as_ref()],
+        bump
+    )]
+    pub token_fee_vault: Account<'info, TokenFeeVault>,
+    
+    /// CHECK: Token account created in the handler, owned by the token fee vault
+    #[account(
+        mut,
+        seeds = [b"fee_vault_token", mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault_token: AccountInfo<'info>,
+    
+    /// CHECK: Stake mint, checked to belong to `token_program`
+    #[account(owner = token_program.key() @ EscrowError::InvalidMint)]
+    pub mint: AccountInfo<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    /// CHECK: SPL Token or Token-2022 program, checked in handler
+    pub token_program: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeTokenVault>, min_stake: u64, max_stake: u64) -> Result<()> {
+    ctx.accounts.config.require_role(Role::Admin, &ctx.accounts.admin.key())?;
+    require!(min_stake > 0 && min_stake <= max_stake, EscrowError::InvalidStakeBounds);
+    check_token_program(&ctx.accounts.token_program)?;
+    
+    let mint = ctx.accounts.mint.key();
+    let token_account_bump = *ctx.bumps.get("fee_vault_token").unwrap();
+    create_token_account(
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.fee_vault_token,
+        &[b"fee_vault_token", mint.as_ref(), &[token_account_bump]],
+        &ctx.accounts.mint,
+        &ctx.accounts.token_fee_vault.key(),
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    
+    let token_fee_vault = &mut ctx.accounts.token_fee_vault;
+    token_fee_vault.mint = mint;
+    token_fee_vault.total_collected = 0;
+    token_fee_vault.min_stake = min_stake;
+    token_fee_vault.max_stake = max_stake;
+    token_fee_vault.bump = *ctx.bumps.get("token_fee_vault").unwrap();
+    token_fee_vault.token_account_bump = token_account_bump;
+    
+    msg!("Token vault initialized for mint {}. Stakes {} - {}", mint, min_stake, max_stake);
+    
+    Ok(())
+}
@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
 use crate::state::*;
 use crate::errors::*;
+use crate::payout::StakeVault;
+use crate::token::*;
 
 #[derive(Accounts)]
 pub struct JoinMatch<'info> {
@@ -11,6 +12,13 @@ pub struct JoinMatch<'info> {
     )]
     pub match_account: Account<'info, Match>,
     
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(Config::PAUSE_JOIN) @ EscrowError::Paused,
+    )]
+    pub config: Account<'info, Config>,
+    
     #[account(
         mut,
         seeds = [b"escrow", match_account.key().as_ref()],
@@ -19,9 +27,20 @@ pub struct JoinMatch<'info> {
     /// CHECK: PDA for holding escrow funds
     pub escrow: AccountInfo<'info>,
     
+    /// Required for correspondence matches, to start the first move's clock
+    #[account(
+        mut,
+        seeds = [b"game", match_account.key().as_ref()],
+        bump = game.bump,
+        constraint = game.belongs_to(&match_account) @ EscrowError::StaleGame
+    )]
+    pub game: Option<Account<'info, CorrespondenceGame>>,
+    
     #[account(mut)]
     pub player_b: Signer<'info>,
     
+    pub token: TokenAccounts<'info>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -41,29 +60,33 @@ pub fn handler(ctx: Context<JoinMatch>) -> Result<()> {
         EscrowError::CannotJoinOwnMatch
     );
     
+    // Private matches can only be joined by the invitee
+    if let Some(invitee) = match_account.invitee {
+        require!(ctx.accounts.player_b.key() == invitee, EscrowError::NotInvited);
+    }
+    
     // Calculate stake amount
     let stake_amount = match_account.stake_amount_lamports();
     
     // Transfer player B's stake to escrow
-    let transfer_ix = system_program::Transfer {
-        from: ctx.accounts.player_b.to_account_info(),
-        to: ctx.accounts.escrow.to_account_info(),
-    };
-    
-    system_program::transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            transfer_ix,
-        ),
-        stake_amount,
-    )?;
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let vault = StakeVault::new(match_account, &system_program, &ctx.accounts.escrow, &ctx.accounts.token)?;
+    vault.deposit(&ctx.accounts.player_b.to_account_info(), stake_amount)?;
     
     // Update match state
     match_account.player_b = Some(ctx.accounts.player_b.key());
     match_account.status = MatchStatus::Active;
+    match_account.started_at = clock.unix_timestamp;
+    
+    // Start the clock for white's first move
+    if match_account.correspondence {
+        let game = ctx.accounts.game.as_mut().ok_or(EscrowError::MissingGameAccount)?;
+        game.turn_started_at = clock.unix_timestamp;
+        msg!("Correspondence game started. First move due by {}", game.move_deadline());
+    }
     
     msg!("Player B joined match. Match is now Active.");
-    msg!("Escrow holds {} lamports", stake_amount * 2);
+    msg!("Escrow holds {}", match_account.stake_liabilities()?);
     
     Ok(())
 }
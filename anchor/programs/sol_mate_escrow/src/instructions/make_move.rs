@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::chess::{GameStatus, Move};
+
+/// Play one move in a correspondence match. The side to move must sign before
+/// their deadline. Checkmate finishes the match for the mover and stalemate or
+/// insufficient material draws it, ready for `confirm_payout` / `settle_draw`.
+#[derive(Accounts)]
+pub struct MakeMove<'info> {
+    #[account(
+        mut,
+        constraint = match_account.status == MatchStatus::Active @ EscrowError::MatchNotActive,
+        constraint = match_account.correspondence @ EscrowError::NotCorrespondenceMatch,
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    #[account(
+        mut,
+        seeds = [b"game", match_account.key().as_ref()],
+        bump = game.bump,
+        constraint = game.belongs_to(&match_account) @ EscrowError::StaleGame
+    )]
+    pub game: Account<'info, CorrespondenceGame>,
+    
+    pub player: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<MakeMove>, raw_move: u16) -> Result<()> {
+    let match_account = &mut ctx.accounts.match_account;
+    let game = &mut ctx.accounts.game;
+    let clock = Clock::get()?;
+    
+    let mut position = game.board.position();
+    let mover = position.side_to_move;
+    require!(
+        game.player(match_account, mover) == Some(ctx.accounts.player.key()),
+        EscrowError::NotYourTurn
+    );
+    require!(
+        clock.unix_timestamp <= game.move_deadline(),
+        EscrowError::MoveDeadlinePassed
+    );
+    
+    let mv = Move::decode(raw_move).ok_or(EscrowError::InvalidMoveEncoding)?;
+    position.play(mv).map_err(|_| EscrowError::IllegalMove)?;
+    
+    game.board.set_position(&position);
+    game.ply = game.ply.saturating_add(1);
+    game.turn_started_at = clock.unix_timestamp;
+    
+    msg!("Move {} played at ply {}", raw_move, game.ply);
+    
+    match position.status() {
+        GameStatus::Checkmate => {
+            match_account.winner = Some(ctx.accounts.player.key());
+            match_account.status = MatchStatus::Finished;
+            match_account.finished_at = clock.unix_timestamp;
+            match_account.end_reason = Some(EndReason::Checkmate);
+            msg!("Checkmate. Winner: {}", ctx.accounts.player.key());
+        }
+        GameStatus::Stalemate | GameStatus::InsufficientMaterial => {
+            match_account.is_draw = true;
+            match_account.status = MatchStatus::Drawn;
+            match_account.finished_at = clock.unix_timestamp;
+            match_account.end_reason = Some(EndReason::Draw);
+            msg!("Game drawn by {:?}", position.status());
+        }
+        GameStatus::Ongoing => {
+            msg!("Next move due by {}", game.move_deadline());
+        }
+    }
+    
+    Ok(())
+}
@@ -6,6 +6,43 @@ pub mod cancel_match;
 pub mod withdraw_fees;
 pub mod abandon_match;
 pub mod force_refund;
+pub mod confirm_result;
+pub mod contest_result;
+pub mod initialize_config;
+pub mod update_config;
+pub mod settle_with_attestation;
+pub mod open_dispute;
+pub mod resolve_dispute;
+pub mod start_replay;
+pub mod replay_moves;
+pub mod prove_checkmate;
+pub mod submit_draw;
+pub mod settle_draw;
+pub mod resign;
+pub mod enable_correspondence;
+pub mod make_move;
+pub mod claim_win_on_time;
+pub mod close_game;
+pub mod settle_mutual;
+pub mod expire_match;
+pub mod update_open_match;
+pub mod abort_match;
+pub mod rematch;
+pub mod close_receipt;
+pub mod initialize_token_vault;
+pub mod update_token_vault;
+pub mod withdraw_token_fees;
+pub mod set_stake_tier;
+pub mod propose_role;
+pub mod accept_role;
+pub mod renounce_role;
+pub mod set_fee_multisig;
+pub mod propose_withdrawal;
+pub mod approve_withdrawal;
+pub mod execute_withdrawal;
+pub mod cancel_withdrawal;
+pub mod set_pause;
+pub mod expire_dispute;
 
 // Re-export the account structs (required by Anchor macros)
 // The handler name collision is expected - each is used with full path
@@ -18,3 +55,40 @@ pub use cancel_match::*;
 pub use withdraw_fees::*;
 pub use abandon_match::*;
 pub use force_refund::*;
+pub use confirm_result::*;
+pub use contest_result::*;
+pub use initialize_config::*;
+pub use update_config::*;
+pub use settle_with_attestation::*;
+pub use open_dispute::*;
+pub use resolve_dispute::*;
+pub use start_replay::*;
+pub use replay_moves::*;
+pub use prove_checkmate::*;
+pub use submit_draw::*;
+pub use settle_draw::*;
+pub use resign::*;
+pub use enable_correspondence::*;
+pub use make_move::*;
+pub use claim_win_on_time::*;
+pub use close_game::*;
+pub use settle_mutual::*;
+pub use expire_match::*;
+pub use update_open_match::*;
+pub use abort_match::*;
+pub use rematch::*;
+pub use close_receipt::*;
+pub use initialize_token_vault::*;
+pub use update_token_vault::*;
+pub use withdraw_token_fees::*;
+pub use set_stake_tier::*;
+pub use propose_role::*;
+pub use accept_role::*;
+pub use renounce_role::*;
+pub use set_fee_multisig::*;
+pub use propose_withdrawal::*;
+pub use approve_withdrawal::*;
+pub use execute_withdrawal::*;
+pub use cancel_withdrawal::*;
+pub use set_pause::*;
+pub use expire_dispute::*;
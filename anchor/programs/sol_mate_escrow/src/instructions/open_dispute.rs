@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::errors::*;
+
+/// Escalate a proposed or contested result to the arbiter.
+/// The disputing player posts the configured bond into the match escrow; it is
+/// forfeited if the arbiter rules against them. If the arbiter has not ruled within
+/// the configured dispute timeout, anyone can refund the match with `expire_dispute`.
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    #[account(
+        mut,
+        constraint = match_account.status == MatchStatus::ResultProposed
+                  || match_account.status == MatchStatus::Contested
+                  @ EscrowError::MatchNotDisputable,
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow", match_account.key().as_ref()],
+        bump = match_account.escrow_bump
+    )]
+    /// CHECK: PDA for holding escrow funds
+    pub escrow: AccountInfo<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        constraint = match_account.opponent_of(&disputer.key()).is_some() @ EscrowError::NotAPlayer
+    )]
+    pub disputer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<OpenDispute>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    require!(config.arbiter != Pubkey::default(), EscrowError::ArbiterNotConfigured);
+    
+    let match_account = &mut ctx.accounts.match_account;
+    let disputer = ctx.accounts.disputer.key();
+    let clock = Clock::get()?;
+    
+    // A pending proposal can only be disputed by the opponent, and only while it could still be contested
+    if match_account.status == MatchStatus::ResultProposed {
+        require!(
+            match_account.proposed_by != Some(disputer),
+            EscrowError::CannotRespondToOwnProposal
+        );
+        require!(
+            match_account.challenge_window_open(clock.unix_timestamp),
+            EscrowError::ChallengeWindowClosed
+        );
+    }
+    
+    // Post the dispute bond into escrow
+    let bond = config.dispute_bond;
+    if bond > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.disputer.to_account_info(),
+                    to: ctx.accounts.escrow.to_account_info(),
+                },
+            ),
+            bond,
+        )?;
+    }
+    
+    match_account.disputed_by = Some(disputer);
+    match_account.dispute_bond = bond;
+    match_account.dispute_deadline = clock.unix_timestamp.saturating_add(config.dispute_timeout);
+    match_account.status = MatchStatus::Disputed;
+    
+    msg!("Dispute opened by {}. Bond: {} lamports", disputer, bond);
+    
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Role};
+use crate::errors::EscrowError;
+
+/// First step of a role rotation: the current holder nominates a successor, who
+/// takes over once they `accept_role`. The admin can nominate a successor for any
+/// other role, to fill it or to replace a compromised key; proposing and accepting
+/// in one transaction leaves the old holder no chance to interfere.
+/// Nominating the default pubkey withdraws a pending nomination.
+#[derive(Accounts)]
+pub struct ProposeRole<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ProposeRole>, role: Role, nominee: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let authority = ctx.accounts.authority.key();
+    let holder = config.role_holder(role);
+    let may_nominate = (holder != Pubkey::default() && authority == holder)
+        || (role != Role::Admin && authority == config.admin);
+    require!(may_nominate, EscrowError::CannotNominateRole);
+    
+    config.pending_roles[role as usize] = nominee;
+    
+    msg!("{:?} nominee set to {}", role, nominee);
+    
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, WithdrawalProposal};
+use crate::errors::EscrowError;
+
+/// Propose a fee withdrawal for the fee multisig to approve. The proposer must be
+/// a multisig signer and counts as the first approval. `mint` selects the mint's
+/// fee vault, or the SOL fee vault for the default pubkey.
+#[derive(Accounts)]
+pub struct ProposeWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = WithdrawalProposal::LEN,
+        seeds = [b"withdrawal".as_ref(), &config.fee_multisig.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, WithdrawalProposal>,
+    
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ProposeWithdrawal>, mint: Pubkey, amount: u64, recipient: Pubkey) -> Result<()> {
+    let clock = Clock::get()?;
+    let config = &mut ctx.accounts.config;
+    let multisig = config.fee_multisig;
+    require!(multisig.is_enabled(), EscrowError::InvalidMultisig);
+    let index = multisig
+        .signer_index(&ctx.accounts.proposer.key())
+        .ok_or(EscrowError::NotMultisigSigner)?;
+    require!(amount > 0, EscrowError::InsufficientFunds);
+    
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.id = multisig.proposal_count;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.mint = mint;
+    proposal.amount = amount;
+    proposal.recipient = recipient;
+    proposal.approvals = 0;
+    proposal.multisig_version = multisig.version;
+    proposal.created_at = clock.unix_timestamp;
+    proposal.approved_at = 0;
+    proposal.expires_at = clock
+        .unix_timestamp
+        .checked_add(multisig.proposal_ttl)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    proposal.bump = *ctx.bumps.get("proposal").unwrap();
+    proposal.approve(index, multisig.threshold, clock.unix_timestamp)?;
+    
+    config.fee_multisig.proposal_count = multisig
+        .proposal_count
+        .checked_add(1)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    
+    msg!("Withdrawal {} proposed: {} of mint {} to {}", proposal.id, amount, mint, recipient);
+    
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use crate::state::*;
+use crate::errors::*;
+use crate::chess::GameStatus;
+use crate::ed25519::require_signed_message;
+use crate::payout::{record_fee, record_stake_fee, transfer_from_escrow, winner_payout, StakeVault};
+use crate::token::*;
+
+/// Settle a contested or disputed match by proving the prover checkmated their opponent.
+///
+/// The replay must belong to the match's current game and its transcript must end in
+/// checkmate delivered by the prover's final move. The transaction must include an
+/// ed25519 verification of a terminal `GameReplay::transcript_message` over the whole
+/// transcript, signed by the loser or the referee. Per-move signatures are not terminal,
+/// so a prefix of the game can never be passed off as its end.
+#[derive(Accounts)]
+pub struct ProveCheckmate<'info> {
+    #[account(
+        mut,
+        constraint = match_account.status == MatchStatus::Contested
+                  || match_account.status == MatchStatus::Disputed
+                  @ EscrowError::MatchNotDisputed,
+        close = player_a
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow", match_account.key().as_ref()],
+        bump = match_account.escrow_bump
+    )]
+    /// CHECK: PDA for holding escrow funds
+    pub escrow: AccountInfo<'info>,
+    
+    #[account(
+        init,
+        payer = prover,
+        space = MatchReceipt::LEN,
+        seeds = [b"receipt", match_account.key().as_ref(), &match_account.nonce.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Account<'info, MatchReceipt>,
+    
+    #[account(
+        mut,
+        seeds = [b"replay", match_account.key().as_ref()],
+        bump = replay.bump,
+        constraint = replay.prover == prover.key() @ EscrowError::ReplayMismatch,
+        constraint = replay.is_current(&match_account) @ EscrowError::StaleReplay,
+        close = prover
+    )]
+    pub replay: Account<'info, GameReplay>,
+    
+    #[account(
+        init_if_needed,
+        payer = prover,
+        space = FeeVault::LEN,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// CHECK: Player A receives rent from closed account
+    #[account(
+        mut,
+        constraint = player_a.key() == match_account.player_a @ EscrowError::InvalidPlayerA
+    )]
+    pub player_a: AccountInfo<'info>,
+    
+    /// The winner, who started the replay
+    #[account(mut)]
+    pub prover: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar, used to find the ed25519 verification
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub token: TokenAccounts<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ProveCheckmate>) -> Result<()> {
+    let replay = &ctx.accounts.replay;
+    let position = replay.board.position();
+    require!(position.status() == GameStatus::Checkmate, EscrowError::NotCheckmate);
+    
+    // The whole transcript, ending with the prover's mating move, must be signed as
+    // terminal by the loser, or by the referee if the loser never signed it
+    let match_account = &mut ctx.accounts.match_account;
+    let match_key = match_account.key();
+    let winner = ctx.accounts.prover.key();
+    let loser = match_account.opponent_of(&winner).ok_or(EscrowError::NotAPlayer)?;
+    let message = replay.transcript_message(&match_key, position.side_to_move.opposite(), true);
+    if require_signed_message(&ctx.accounts.instructions, &loser, &message).is_err() {
+        let referee = ctx.accounts.config.referee;
+        require!(referee != Pubkey::default(), EscrowError::MissingSignatureVerification);
+        require_signed_message(&ctx.accounts.instructions, &referee, &message)?;
+    }
+    
+    match_account.winner = Some(winner);
+    match_account.status = MatchStatus::Finished;
+    match_account.finished_at = Clock::get()?.unix_timestamp;
+    match_account.end_reason = Some(EndReason::Checkmate);
+    
+    msg!("Checkmate proven at ply {}. Winner: {}", replay.ply, winner);
+    
+    // A bond posted by the loser is forfeited, one posted by the winner is returned
+    let bond = match_account.dispute_bond;
+    let (forfeited_bond, bond_refund) = match match_account.disputed_by {
+        Some(disputer) if disputer == winner => (0, bond),
+        Some(_) => (bond, 0),
+        None => (0, 0),
+    };
+    
+    let (fee_amount, winner_amount) = winner_payout(match_account)?;
+    
+    msg!("Fee: {}", fee_amount);
+    msg!("Payout to winner: {}", winner_amount);
+    
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let vault = StakeVault::new(match_account, &system_program, &ctx.accounts.escrow, &ctx.accounts.token)?;
+    
+    // Only settle against the expected escrow balance
+    vault.sweep_surplus(match_account, &ctx.accounts.player_a)?;
+    
+    let fee_received = vault.pay_fee(&ctx.accounts.fee_vault.to_account_info(), fee_amount)?;
+    
+    vault.pay_player(&ctx.accounts.prover.to_account_info(), winner_amount)?;
+    vault.close(&ctx.accounts.player_a)?;
+    
+    // Dispute bonds are always held in lamports
+    let fee_vault_info = ctx.accounts.fee_vault.to_account_info();
+    let prover_info = ctx.accounts.prover.to_account_info();
+    for (to, amount) in [(&fee_vault_info, forfeited_bond), (&prover_info, bond_refund)] {
+        if amount > 0 {
+            transfer_from_escrow(
+                &system_program,
+                &ctx.accounts.escrow,
+                to,
+                &match_key,
+                match_account.escrow_bump,
+                amount,
+            )?;
+        }
+    }
+    
+    let paid = if winner == match_account.player_a {
+        [winner_amount, 0]
+    } else {
+        [0, winner_amount]
+    };
+    ctx.accounts.receipt.record(
+        match_key,
+        match_account,
+        MatchOutcome::Won,
+        paid,
+        fee_amount,
+        ctx.accounts.prover.key(),
+    )?;
+    
+    record_stake_fee(
+        &mut ctx.accounts.fee_vault,
+        *ctx.bumps.get("fee_vault").unwrap(),
+        ctx.accounts.token.token_fee_vault.as_mut(),
+        match_account,
+        fee_received,
+    )?;
+    record_fee(
+        &mut ctx.accounts.fee_vault,
+        *ctx.bumps.get("fee_vault").unwrap(),
+        forfeited_bond,
+    )?;
+    
+    msg!("Checkmate settlement complete. Match and replay accounts closed.");
+    
+    Ok(())
+}
@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use crate::state::*;
+use crate::errors::*;
+use crate::ed25519::require_signed_message;
+use crate::payout::{draw_refund, record_stake_fee, winner_payout, StakeVault};
+use crate::token::*;
+
+/// Settle a Finished or Drawn match and immediately start a rematch in the same
+/// account, with the same players and stake, colors swapped and a new game index.
+///
+/// Each player's share of the settled pot is rolled into their new stake: any
+/// excess is paid out, any shortfall is topped up by the player, who must then
+/// sign the transaction. A player who does not sign the transaction consents with
+/// an ed25519-verified signature over `Match::rematch_message` instead.
+#[derive(Accounts)]
+pub struct Rematch<'info> {
+    #[account(
+        mut,
+        constraint = match_account.status == MatchStatus::Finished
+                  || match_account.status == MatchStatus::Drawn
+                  @ EscrowError::MatchNotFinished,
+        constraint = !match_account.correspondence @ EscrowError::CorrespondenceMatch,
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(Config::PAUSE_CREATE) @ EscrowError::Paused,
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow", match_account.key().as_ref()],
+        bump = match_account.escrow_bump
+    )]
+    /// CHECK: PDA for holding escrow funds
+    pub escrow: AccountInfo<'info>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = MatchReceipt::LEN,
+        seeds = [b"receipt", match_account.key().as_ref(), &match_account.nonce.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Account<'info, MatchReceipt>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = FeeVault::LEN,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    /// CHECK: Player A; signature or signed consent checked in handler
+    #[account(
+        mut,
+        constraint = player_a.key() == match_account.player_a @ EscrowError::InvalidPlayerA
+    )]
+    pub player_a: AccountInfo<'info>,
+    
+    /// CHECK: Player B; signature or signed consent checked in handler
+    #[account(
+        mut,
+        constraint = match_account.player_b.is_some() && player_b.key() == match_account.player_b.unwrap() @ EscrowError::InvalidPlayerB
+    )]
+    pub player_b: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar, used to find signed rematch consents
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub token: TokenAccounts<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<Rematch>) -> Result<()> {
+    let match_account = &mut ctx.accounts.match_account;
+    let match_key = match_account.key();
+    let stake_amount = match_account.stake_amount_lamports();
+    
+    // What each player would receive if the match were settled now
+    let (owed_a, owed_b, fee_amount) = if match_account.status == MatchStatus::Drawn {
+        let (fee_per_player, refund_amount) = draw_refund(stake_amount, match_account.draw_fee_bps)?;
+        (refund_amount, refund_amount, fee_per_player * 2)
+    } else {
+        let winner = match_account.winner.ok_or(EscrowError::MatchNotFinished)?;
+        let (fee_amount, payout_amount) = winner_payout(match_account)?;
+        if winner == match_account.player_a {
+            (payout_amount, 0, fee_amount)
+        } else {
+            (0, payout_amount, fee_amount)
+        }
+    };
+    
+    msg!("Settling game {} for rematch. Fee: {}", match_account.game_index, fee_amount);
+    
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let vault = StakeVault::new(match_account, &system_program, &ctx.accounts.escrow, &ctx.accounts.token)?;
+    
+    // Only settle against the expected escrow balance
+    vault.sweep_surplus(match_account, &ctx.accounts.player_a)?;
+    
+    let fee_received = vault.pay_fee(&ctx.accounts.fee_vault.to_account_info(), fee_amount)?;
+    
+    let consent = match_account.rematch_message(&match_key);
+    for (player, owed) in [(&ctx.accounts.player_a, owed_a), (&ctx.accounts.player_b, owed_b)] {
+        if !player.is_signer {
+            require_signed_message(&ctx.accounts.instructions, player.key, &consent)
+                .map_err(|_| EscrowError::RematchConsentMissing)?;
+        }
+        
+        if owed >= stake_amount {
+            // Roll the stake over and pay out the rest
+            let excess = owed - stake_amount;
+            if excess > 0 {
+                vault.pay_player(player, excess)?;
+            }
+            msg!("{} rolls over {}, receives {}", player.key(), stake_amount, excess);
+        } else {
+            // Top up the shortfall from the player
+            require!(player.is_signer, EscrowError::RematchConsentMissing);
+            let shortfall = stake_amount - owed;
+            vault.deposit(player, shortfall)?;
+            msg!("{} tops up {}", player.key(), shortfall);
+        }
+    }
+    
+    let outcome = if match_account.status == MatchStatus::Drawn {
+        MatchOutcome::Drawn
+    } else {
+        MatchOutcome::Won
+    };
+    ctx.accounts.receipt.record(
+        match_key,
+        match_account,
+        outcome,
+        [owed_a, owed_b],
+        fee_amount,
+        ctx.accounts.payer.key(),
+    )?;
+    
+    record_stake_fee(
+        &mut ctx.accounts.fee_vault,
+        *ctx.bumps.get("fee_vault").unwrap(),
+        ctx.accounts.token.token_fee_vault.as_mut(),
+        match_account,
+        fee_received,
+    )?;
+    
+    // Reset the match for the next game
+    let clock = Clock::get()?;
+    match_account.status = MatchStatus::Active;
+    match_account.winner = None;
+    match_account.is_draw = false;
+    match_account.end_reason = None;
+    match_account.proposed_by = None;
+    match_account.proposed_at = 0;
+    match_account.disputed_by = None;
+    match_account.dispute_bond = 0;
+    match_account.dispute_deadline = 0;
+    match_account.started_at = clock.unix_timestamp;
+    match_account.finished_at = 0;
+    match_account.game_index = match_account
+        .game_index
+        .checked_add(1)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    match_account.nonce = match_account.game_nonce();
+    match_account.player_a_white = !match_account.player_a_white;
+    
+    msg!(
+        "Rematch started. Game {}, player A plays {}",
+        match_account.game_index,
+        if match_account.player_a_white { "white" } else { "black" }
+    );
+    
+    Ok(())
+}
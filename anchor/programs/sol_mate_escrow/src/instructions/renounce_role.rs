@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Role};
+use crate::errors::EscrowError;
+
+/// Let the holder of a role give it up, leaving it vacant until the admin fills it.
+/// A vacant referee disables attestations. The admin and arbiter roles can only be
+/// rotated, never renounced: open disputes must always have someone to resolve them.
+#[derive(Accounts)]
+pub struct RenounceRole<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RenounceRole>, role: Role) -> Result<()> {
+    require!(
+        role != Role::Admin && role != Role::Arbiter,
+        EscrowError::CannotRenounceRole
+    );
+    
+    let config = &mut ctx.accounts.config;
+    config.require_role(role, &ctx.accounts.authority.key())?;
+    config.set_role_holder(role, Pubkey::default());
+    config.pending_roles[role as usize] = Pubkey::default();
+    
+    msg!("{:?} role renounced", role);
+    
+    Ok(())
+}
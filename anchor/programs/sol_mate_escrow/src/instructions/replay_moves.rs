@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::chess::Move;
+
+/// Apply the next chunk of compact UCI moves to a replay.
+/// Every move must be legal; the transcript hash is extended as moves are played.
+#[derive(Accounts)]
+pub struct ReplayMoves<'info> {
+    #[account(
+        mut,
+        seeds = [b"replay", replay.match_account.as_ref()],
+        bump = replay.bump,
+        constraint = replay.prover == prover.key() @ EscrowError::ReplayMismatch
+    )]
+    pub replay: Account<'info, GameReplay>,
+    
+    pub prover: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ReplayMoves>, moves: Vec<u16>) -> Result<()> {
+    let replay = &mut ctx.accounts.replay;
+    let mut position = replay.board.position();
+    
+    for raw_move in moves {
+        let mv = Move::decode(raw_move).ok_or(EscrowError::InvalidMoveEncoding)?;
+        position.play(mv).map_err(|_| EscrowError::IllegalMove)?;
+        replay.record_move(raw_move);
+    }
+    
+    replay.board.set_position(&position);
+    
+    msg!("Replay at ply {}", replay.ply);
+    
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::payout::{record_stake_fee, winner_payout, StakeVault};
+use crate::token::*;
+
+/// Resign a match, awarding it to the opponent and paying out immediately.
+/// Only the losing player can sign this, so no challenge window applies.
+#[derive(Accounts)]
+pub struct Resign<'info> {
+    #[account(
+        mut,
+        constraint = match_account.status == MatchStatus::Active
+                  || match_account.status == MatchStatus::ResultProposed
+                  || match_account.status == MatchStatus::Contested
+                  @ EscrowError::MatchNotActive,
+        close = player_a
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow", match_account.key().as_ref()],
+        bump = match_account.escrow_bump
+    )]
+    /// CHECK: PDA for holding escrow funds
+    pub escrow: AccountInfo<'info>,
+    
+    #[account(
+        init,
+        payer = resigner,
+        space = MatchReceipt::LEN,
+        seeds = [b"receipt", match_account.key().as_ref(), &match_account.nonce.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Account<'info, MatchReceipt>,
+    
+    #[account(
+        init_if_needed,
+        payer = resigner,
+        space = FeeVault::LEN,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    /// CHECK: Opponent of the resigning player, receives payout
+    #[account(
+        mut,
+        constraint = match_account.opponent_of(&resigner.key()) == Some(winner.key()) @ EscrowError::InvalidWinner
+    )]
+    pub winner: AccountInfo<'info>,
+    
+    /// CHECK: Player A receives rent from closed account
+    #[account(
+        mut,
+        constraint = player_a.key() == match_account.player_a @ EscrowError::InvalidPlayerA
+    )]
+    pub player_a: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub resigner: Signer<'info>,
+    
+    /// Correspondence game of the match, closed along with it
+    #[account(
+        mut,
+        seeds = [b"game", match_account.key().as_ref()],
+        bump = game.bump,
+        close = player_a
+    )]
+    pub game: Option<Account<'info, CorrespondenceGame>>,
+    
+    pub token: TokenAccounts<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<Resign>) -> Result<()> {
+    // Correspondence games are closed along with their match
+    require!(
+        !ctx.accounts.match_account.correspondence || ctx.accounts.game.is_some(),
+        EscrowError::MissingGameAccount
+    );
+    
+    let match_account = &mut ctx.accounts.match_account;
+    let winner = ctx.accounts.winner.key();
+    
+    match_account.winner = Some(winner);
+    match_account.is_draw = false;
+    match_account.status = MatchStatus::Finished;
+    match_account.finished_at = Clock::get()?.unix_timestamp;
+    match_account.end_reason = Some(EndReason::Resignation);
+    
+    msg!("{} resigned. Winner: {}", ctx.accounts.resigner.key(), winner);
+    
+    // Calculate amounts
+    let (fee_amount, payout_amount) = winner_payout(match_account)?;
+    
+    msg!("Fee: {} lamports", fee_amount);
+    msg!("Payout to winner: {} lamports", payout_amount);
+    
+    let match_key = match_account.key();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let vault = StakeVault::new(match_account, &system_program, &ctx.accounts.escrow, &ctx.accounts.token)?;
+    
+    // Only settle against the expected escrow balance
+    vault.sweep_surplus(match_account, &ctx.accounts.player_a)?;
+    
+    let fee_received = vault.pay_fee(&ctx.accounts.fee_vault.to_account_info(), fee_amount)?;
+    
+    vault.pay_player(&ctx.accounts.winner, payout_amount)?;
+    vault.close(&ctx.accounts.player_a)?;
+    
+    let paid = if winner == match_account.player_a {
+        [payout_amount, 0]
+    } else {
+        [0, payout_amount]
+    };
+    ctx.accounts.receipt.record(
+        match_key,
+        match_account,
+        MatchOutcome::Won,
+        paid,
+        fee_amount,
+        ctx.accounts.resigner.key(),
+    )?;
+    
+    record_stake_fee(
+        &mut ctx.accounts.fee_vault,
+        *ctx.bumps.get("fee_vault").unwrap(),
+        ctx.accounts.token.token_fee_vault.as_mut(),
+        match_account,
+        fee_received,
+    )?;
+    
+    msg!("Payout complete. Match account closed.");
+    
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::payout::{draw_refund, record_fee, record_stake_fee, stake_fees, transfer_from_escrow, winner_payout, StakeVault};
+use crate::token::*;
+
+/// Arbiter ruling on a disputed match. Distributes the pot according to the
+/// resolution, returns or forfeits the dispute bond, and closes the match.
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        mut,
+        constraint = match_account.status == MatchStatus::Disputed @ EscrowError::MatchNotDisputed,
+        close = player_a
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow", match_account.key().as_ref()],
+        bump = match_account.escrow_bump
+    )]
+    /// CHECK: PDA for holding escrow funds
+    pub escrow: AccountInfo<'info>,
+    
+    #[account(
+        init,
+        payer = arbiter,
+        space = MatchReceipt::LEN,
+        seeds = [b"receipt", match_account.key().as_ref(), &match_account.nonce.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Account<'info, MatchReceipt>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        init_if_needed,
+        payer = arbiter,
+        space = FeeVault::LEN,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    /// CHECK: Player A receives their share and rent from closed account
+    #[account(
+        mut,
+        constraint = player_a.key() == match_account.player_a @ EscrowError::InvalidPlayerA
+    )]
+    pub player_a: AccountInfo<'info>,
+    
+    /// CHECK: Player B receives their share
+    #[account(
+        mut,
+        constraint = match_account.player_b.is_some() && player_b.key() == match_account.player_b.unwrap() @ EscrowError::InvalidPlayerB
+    )]
+    pub player_b: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub arbiter: Signer<'info>,
+    
+    pub token: TokenAccounts<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ResolveDispute>, resolution: DisputeResolution) -> Result<()> {
+    ctx.accounts.config.require_role(Role::Arbiter, &ctx.accounts.arbiter.key())?;
+    
+    let match_account = &mut ctx.accounts.match_account;
+    let player_a = match_account.player_a;
+    let player_b = match_account.player_b.ok_or(EscrowError::InvalidPlayerB)?;
+    let disputer = match_account.disputed_by.ok_or(EscrowError::MatchNotDisputed)?;
+    
+    let stake_amount = match_account.stake_amount_lamports();
+    let (platform_fee, winner_amount) = winner_payout(match_account)?;
+    let (draw_fee, draw_amount) = draw_refund(stake_amount, match_account.draw_fee_bps)?;
+    
+    // Work out each side's share of the pot
+    let (to_player_a, to_player_b, fee_amount) = match resolution {
+        DisputeResolution::PlayerA => (winner_amount, 0, platform_fee),
+        DisputeResolution::PlayerB => (0, winner_amount, platform_fee),
+        DisputeResolution::Draw => (draw_amount, draw_amount, draw_fee * 2),
+        DisputeResolution::Split => {
+            // Each player gets their own stake back minus their own side's fee
+            let [host_fee, joiner_fee] = stake_fees(match_account)?;
+            (stake_amount - host_fee, stake_amount - joiner_fee, platform_fee)
+        }
+    };
+    
+    match_account.is_draw = resolution == DisputeResolution::Draw;
+    match_account.finished_at = Clock::get()?.unix_timestamp;
+    match_account.end_reason = if match_account.is_draw { Some(EndReason::Draw) } else { None };
+    match_account.winner = match resolution {
+        DisputeResolution::PlayerA => Some(player_a),
+        DisputeResolution::PlayerB => Some(player_b),
+        DisputeResolution::Draw | DisputeResolution::Split => None,
+    };
+    
+    // The disputer loses their bond only if the arbiter ruled for their opponent
+    let disputer_lost = match_account.winner.is_some() && match_account.winner != Some(disputer);
+    let bond = match_account.dispute_bond;
+    let (forfeited_bond, bond_refund) = if disputer_lost { (bond, 0) } else { (0, bond) };
+    
+    msg!("Dispute resolved: {:?}", resolution);
+    msg!("Player A: {}, Player B: {}", to_player_a, to_player_b);
+    msg!("Fee: {} (bond forfeited: {})", fee_amount, disputer_lost);
+    
+    let match_key = match_account.key();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let vault = StakeVault::new(match_account, &system_program, &ctx.accounts.escrow, &ctx.accounts.token)?;
+    
+    // Only settle against the expected escrow balance
+    vault.sweep_surplus(match_account, &ctx.accounts.player_a)?;
+    let fee_vault_info = ctx.accounts.fee_vault.to_account_info();
+    let disputer_info = if disputer == player_a {
+        &ctx.accounts.player_a
+    } else {
+        &ctx.accounts.player_b
+    };
+    
+    let fee_received = vault.pay_fee(&fee_vault_info, fee_amount)?;
+    vault.pay_player(&ctx.accounts.player_a, to_player_a)?;
+    vault.pay_player(&ctx.accounts.player_b, to_player_b)?;
+    vault.close(&ctx.accounts.player_a)?;
+    
+    // Dispute bonds are always held in lamports
+    for (to, amount) in [(&fee_vault_info, forfeited_bond), (disputer_info, bond_refund)] {
+        if amount > 0 {
+            transfer_from_escrow(
+                &system_program,
+                &ctx.accounts.escrow,
+                to,
+                &match_key,
+                match_account.escrow_bump,
+                amount,
+            )?;
+        }
+    }
+    
+    let outcome = match resolution {
+        DisputeResolution::PlayerA | DisputeResolution::PlayerB => MatchOutcome::Won,
+        DisputeResolution::Draw => MatchOutcome::Drawn,
+        DisputeResolution::Split => MatchOutcome::Split,
+    };
+    ctx.accounts.receipt.record(
+        match_key,
+        match_account,
+        outcome,
+        [to_player_a, to_player_b],
+        fee_amount,
+        ctx.accounts.arbiter.key(),
+    )?;
+    
+    record_stake_fee(
+        &mut ctx.accounts.fee_vault,
+        *ctx.bumps.get("fee_vault").unwrap(),
+        ctx.accounts.token.token_fee_vault.as_mut(),
+        match_account,
+        fee_received,
+    )?;
+    record_fee(
+        &mut ctx.accounts.fee_vault,
+        *ctx.bumps.get("fee_vault").unwrap(),
+        forfeited_bond,
+    )?;
+    
+    msg!("Dispute settled. Match account closed.");
+    
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, FeeMultisig, Role};
+use crate::errors::EscrowError;
+
+/// New fee multisig settings. A threshold of 0 disables the multisig.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct FeeMultisigParams {
+    pub signers: [Pubkey; FeeMultisig::MAX_SIGNERS],
+    pub threshold: u8,
+    pub delay: i64,
+    pub proposal_ttl: i64,
+}
+
+/// Configure the M-of-N signers that approve fee withdrawals.
+/// Signed by the fee withdrawer; while a multisig is enabled, at least its current
+/// threshold of signers must also sign, passed as remaining accounts.
+/// Every change invalidates open withdrawal proposals.
+#[derive(Accounts)]
+pub struct SetFeeMultisig<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub fee_withdrawer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetFeeMultisig>, params: FeeMultisigParams) -> Result<()> {
+    ctx.accounts.config.require_role(Role::FeeWithdrawer, &ctx.accounts.fee_withdrawer.key())?;
+    
+    let config = &mut ctx.accounts.config;
+    let current = config.fee_multisig;
+    if current.is_enabled() {
+        // Count distinct current signers among the co-signers
+        let mut approvals = 0u8;
+        for account in ctx.remaining_accounts.iter().filter(|account| account.is_signer) {
+            if let Some(index) = current.signer_index(account.key) {
+                approvals |= 1 << index;
+            }
+        }
+        require!(
+            approvals.count_ones() >= current.threshold as u32,
+            EscrowError::MultisigThresholdNotMet
+        );
+    }
+    
+    let multisig = FeeMultisig {
+        signers: params.signers,
+        threshold: params.threshold,
+        delay: params.delay,
+        proposal_ttl: params.proposal_ttl,
+        version: current.version.wrapping_add(1),
+        proposal_count: current.proposal_count,
+    };
+    require!(multisig.is_valid(), EscrowError::InvalidMultisig);
+    config.fee_multisig = multisig;
+    
+    msg!(
+        "Fee multisig set: {} signatures required, delay {}s (version {})",
+        multisig.threshold,
+        multisig.delay,
+        multisig.version
+    );
+    
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Role};
+use crate::errors::EscrowError;
+
+/// Pause or resume instructions that take in new money or new results.
+/// `paused` is a bitmask of `Config::PAUSE_*` flags; `Config::PAUSE_ALL` is the
+/// global switch and 0 resumes everything. Only the pauser can call this.
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub pauser: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetPause>, paused: u8) -> Result<()> {
+    ctx.accounts.config.require_role(Role::Pauser, &ctx.accounts.pauser.key())?;
+    require!(paused & !Config::PAUSE_ALL == 0, EscrowError::InvalidPauseFlags);
+    
+    ctx.accounts.config.paused = paused;
+    
+    msg!("Pause flags set to {:#05b}", paused);
+    
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Role, StakeTier};
+use crate::errors::EscrowError;
+
+/// Add, re-price or disable an entry of the stake tier table.
+/// Matches snapshot their stake at creation, so this never affects existing matches.
+#[derive(Accounts)]
+pub struct SetStakeTier<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetStakeTier>, stake_tier: u8, lamports: u64, enabled: bool) -> Result<()> {
+    ctx.accounts.config.require_role(Role::Admin, &ctx.accounts.admin.key())?;
+    
+    let config = &mut ctx.accounts.config;
+    require!((stake_tier as usize) < Config::MAX_STAKE_TIERS, EscrowError::InvalidStakeTier);
+    if enabled {
+        require!(Config::tier_allowed(stake_tier), EscrowError::InvalidStakeTier);
+        require!(config.stake_valid(lamports), EscrowError::InvalidStakeAmount);
+    }
+    
+    config.stake_tiers[stake_tier as usize] = StakeTier { lamports, enabled };
+    
+    msg!("Stake tier {} set to {} lamports (enabled: {})", stake_tier, lamports, enabled);
+    
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::payout::{draw_refund, record_stake_fee, StakeVault};
+use crate::token::*;
+
+/// Return both stakes of a drawn match, minus the match's draw fee.
+/// Works for a confirmed draw, or a proposed draw whose challenge window elapsed uncontested.
+#[derive(Accounts)]
+pub struct SettleDraw<'info> {
+    #[account(
+        mut,
+        constraint = match_account.status == MatchStatus::Drawn
+                  || (match_account.status == MatchStatus::ResultProposed && match_account.is_draw)
+                  @ EscrowError::MatchNotDrawn,
+        close = player_a
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow", match_account.key().as_ref()],
+        bump = match_account.escrow_bump
+    )]
+    /// CHECK: PDA for holding escrow funds
+    pub escrow: AccountInfo<'info>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = MatchReceipt::LEN,
+        seeds = [b"receipt", match_account.key().as_ref(), &match_account.nonce.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Account<'info, MatchReceipt>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = FeeVault::LEN,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    /// CHECK: Player A receives their stake back and rent from closed account
+    #[account(
+        mut,
+        constraint = player_a.key() == match_account.player_a @ EscrowError::InvalidPlayerA
+    )]
+    pub player_a: AccountInfo<'info>,
+    
+    /// CHECK: Player B receives their stake back
+    #[account(
+        mut,
+        constraint = match_account.player_b.is_some() && player_b.key() == match_account.player_b.unwrap() @ EscrowError::InvalidPlayerB
+    )]
+    pub player_b: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// Correspondence game of the match, closed along with it
+    #[account(
+        mut,
+        seeds = [b"game", match_account.key().as_ref()],
+        bump = game.bump,
+        close = player_a
+    )]
+    pub game: Option<Account<'info, CorrespondenceGame>>,
+    
+    pub token: TokenAccounts<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SettleDraw>) -> Result<()> {
+    // Correspondence games are closed along with their match
+    require!(
+        !ctx.accounts.match_account.correspondence || ctx.accounts.game.is_some(),
+        EscrowError::MissingGameAccount
+    );
+    
+    let match_account = &mut ctx.accounts.match_account;
+    
+    // An unconfirmed proposal only becomes payable once nobody contested it in time
+    if match_account.status == MatchStatus::ResultProposed {
+        let clock = Clock::get()?;
+        require!(
+            !match_account.challenge_window_open(clock.unix_timestamp),
+            EscrowError::ChallengeWindowOpen
+        );
+        match_account.finished_at = clock.unix_timestamp;
+    }
+    match_account.status = MatchStatus::Drawn;
+    match_account.end_reason = Some(EndReason::Draw);
+    
+    // Calculate amounts
+    let stake_amount = match_account.stake_amount_lamports();
+    let (fee_per_player, refund_amount) = draw_refund(stake_amount, match_account.draw_fee_bps)?;
+    let fee_amount = fee_per_player
+        .checked_mul(2)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    
+    msg!("Draw fee ({} bps per stake): {} lamports", match_account.draw_fee_bps, fee_amount);
+    msg!("Refund per player: {} lamports", refund_amount);
+    
+    let match_key = match_account.key();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let vault = StakeVault::new(match_account, &system_program, &ctx.accounts.escrow, &ctx.accounts.token)?;
+    
+    // Only settle against the expected escrow balance
+    vault.sweep_surplus(match_account, &ctx.accounts.player_a)?;
+    
+    let fee_received = vault.pay_fee(&ctx.accounts.fee_vault.to_account_info(), fee_amount)?;
+    for player in [&ctx.accounts.player_a, &ctx.accounts.player_b] {
+        vault.pay_player(player, refund_amount)?;
+    }
+    vault.close(&ctx.accounts.player_a)?;
+    
+    ctx.accounts.receipt.record(
+        match_key,
+        match_account,
+        MatchOutcome::Drawn,
+        [refund_amount, refund_amount],
+        fee_amount,
+        ctx.accounts.payer.key(),
+    )?;
+    
+    record_stake_fee(
+        &mut ctx.accounts.fee_vault,
+        *ctx.bumps.get("fee_vault").unwrap(),
+        ctx.accounts.token.token_fee_vault.as_mut(),
+        match_account,
+        fee_received,
+    )?;
+    
+    msg!("Draw settled. Match account closed.");
+    
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use crate::state::*;
+use crate::errors::*;
+use crate::ed25519::require_signed_message;
+use crate::payout::{draw_refund, record_stake_fee, winner_payout, StakeVault};
+use crate::token::*;
+
+/// Settle a match from a result signed off-chain by both players.
+/// The transaction must include ed25519 precompile instructions verifying both
+/// players' signatures over `Match::result_message`. Anyone can submit it.
+/// A draw is signed with the default pubkey as winner and `EndReason::Draw`.
+#[derive(Accounts)]
+pub struct SettleMutual<'info> {
+    #[account(
+        mut,
+        constraint = match_account.status == MatchStatus::Active
+                  || match_account.status == MatchStatus::ResultProposed
+                  || match_account.status == MatchStatus::Contested
+                  @ EscrowError::MatchNotActive,
+        constraint = !match_account.correspondence @ EscrowError::CorrespondenceMatch,
+        close = player_a
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(Config::PAUSE_RESULTS) @ EscrowError::Paused,
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow", match_account.key().as_ref()],
+        bump = match_account.escrow_bump
+    )]
+    /// CHECK: PDA for holding escrow funds
+    pub escrow: AccountInfo<'info>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = MatchReceipt::LEN,
+        seeds = [b"receipt", match_account.key().as_ref(), &match_account.nonce.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Account<'info, MatchReceipt>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = FeeVault::LEN,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    /// CHECK: Player A receives payout or refund and rent from closed account
+    #[account(
+        mut,
+        constraint = player_a.key() == match_account.player_a @ EscrowError::InvalidPlayerA
+    )]
+    pub player_a: AccountInfo<'info>,
+    
+    /// CHECK: Player B receives payout or refund
+    #[account(
+        mut,
+        constraint = match_account.player_b.is_some() && player_b.key() == match_account.player_b.unwrap() @ EscrowError::InvalidPlayerB
+    )]
+    pub player_b: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar, used to find the ed25519 verifications
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub token: TokenAccounts<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<SettleMutual>,
+    winner: Pubkey,
+    end_reason: EndReason,
+) -> Result<()> {
+    let match_account = &mut ctx.accounts.match_account;
+    let player_a = match_account.player_a;
+    let player_b = match_account.player_b.ok_or(EscrowError::MatchNotActive)?;
+    
+    // A draw has no winner; anything else must name one of the players
+    let is_draw = end_reason == EndReason::Draw;
+    if is_draw {
+        require!(winner == Pubkey::default(), EscrowError::InvalidWinner);
+    } else {
+        require!(winner == player_a || winner == player_b, EscrowError::InvalidWinner);
+    }
+    
+    // Verify both players signed this exact result
+    let match_key = match_account.key();
+    let message = match_account.result_message(&match_key, &winner, end_reason);
+    require_signed_message(&ctx.accounts.instructions, &player_a, &message)?;
+    require_signed_message(&ctx.accounts.instructions, &player_b, &message)?;
+    
+    match_account.end_reason = Some(end_reason);
+    match_account.finished_at = Clock::get()?.unix_timestamp;
+    let stake_amount = match_account.stake_amount_lamports();
+    let (to_player_a, to_player_b, fee_amount) = if is_draw {
+        let (fee_per_player, refund_amount) = draw_refund(stake_amount, match_account.draw_fee_bps)?;
+        match_account.is_draw = true;
+        match_account.winner = None;
+        match_account.status = MatchStatus::Drawn;
+        msg!("Mutually agreed draw.");
+        (refund_amount, refund_amount, fee_per_player * 2)
+    } else {
+        let (fee_amount, payout_amount) = winner_payout(match_account)?;
+        match_account.is_draw = false;
+        match_account.winner = Some(winner);
+        match_account.status = MatchStatus::Finished;
+        msg!("Mutually agreed result. Winner: {} ({:?})", winner, end_reason);
+        if winner == player_a {
+            (payout_amount, 0, fee_amount)
+        } else {
+            (0, payout_amount, fee_amount)
+        }
+    };
+    
+    msg!("Fee: {} lamports", fee_amount);
+    msg!("Player A: {} lamports, Player B: {} lamports", to_player_a, to_player_b);
+    
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let vault = StakeVault::new(match_account, &system_program, &ctx.accounts.escrow, &ctx.accounts.token)?;
+    
+    // Only settle against the expected escrow balance
+    vault.sweep_surplus(match_account, &ctx.accounts.player_a)?;
+    
+    let fee_received = vault.pay_fee(&ctx.accounts.fee_vault.to_account_info(), fee_amount)?;
+    vault.pay_player(&ctx.accounts.player_a, to_player_a)?;
+    vault.pay_player(&ctx.accounts.player_b, to_player_b)?;
+    vault.close(&ctx.accounts.player_a)?;
+    
+    let outcome = if is_draw { MatchOutcome::Drawn } else { MatchOutcome::Won };
+    ctx.accounts.receipt.record(
+        match_key,
+        match_account,
+        outcome,
+        [to_player_a, to_player_b],
+        fee_amount,
+        ctx.accounts.payer.key(),
+    )?;
+    
+    record_stake_fee(
+        &mut ctx.accounts.fee_vault,
+        *ctx.bumps.get("fee_vault").unwrap(),
+        ctx.accounts.token.token_fee_vault.as_mut(),
+        match_account,
+        fee_received,
+    )?;
+    
+    msg!("Mutual settlement complete. Match account closed.");
+    
+    Ok(())
+}
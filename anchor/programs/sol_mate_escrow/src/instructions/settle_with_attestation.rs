@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use crate::state::*;
+use crate::errors::*;
+use crate::ed25519::require_signed_message;
+use crate::payout::{record_stake_fee, winner_payout, StakeVault};
+use crate::token::*;
+
+/// Settle a match from a result attested by the configured referee.
+/// The transaction must include an ed25519 precompile instruction verifying the
+/// referee's signature over `Match::result_message`. Anyone can submit it.
+#[derive(Accounts)]
+pub struct SettleWithAttestation<'info> {
+    #[account(
+        mut,
+        constraint = match_account.status == MatchStatus::Active
+                  || match_account.status == MatchStatus::ResultProposed
+                  || match_account.status == MatchStatus::Contested
+                  @ EscrowError::MatchNotActive,
+        constraint = !match_account.correspondence @ EscrowError::CorrespondenceMatch,
+        close = player_a
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow", match_account.key().as_ref()],
+        bump = match_account.escrow_bump
+    )]
+    /// CHECK: PDA for holding escrow funds
+    pub escrow: AccountInfo<'info>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = MatchReceipt::LEN,
+        seeds = [b"receipt", match_account.key().as_ref(), &match_account.nonce.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Account<'info, MatchReceipt>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(Config::PAUSE_RESULTS) @ EscrowError::Paused,
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = FeeVault::LEN,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    #[account(mut)]
+    /// CHECK: Winner receives payout, validated against the attested winner
+    pub winner: AccountInfo<'info>,
+    
+    /// CHECK: Player A receives rent from closed account
+    #[account(
+        mut,
+        constraint = player_a.key() == match_account.player_a @ EscrowError::InvalidPlayerA
+    )]
+    pub player_a: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar, used to find the ed25519 verification
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub token: TokenAccounts<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<SettleWithAttestation>,
+    winner: Pubkey,
+    end_reason: EndReason,
+) -> Result<()> {
+    let referee = ctx.accounts.config.referee;
+    require!(referee != Pubkey::default(), EscrowError::RefereeNotConfigured);
+    
+    let match_account = &mut ctx.accounts.match_account;
+    
+    // Validate winner is one of the players
+    let player_b = match_account.player_b.ok_or(EscrowError::MatchNotActive)?;
+    require!(
+        winner == match_account.player_a || winner == player_b,
+        EscrowError::InvalidWinner
+    );
+    require!(winner == ctx.accounts.winner.key(), EscrowError::InvalidWinner);
+    require!(end_reason != EndReason::Draw, EscrowError::InvalidEndReason);
+    
+    // Verify the referee signed this exact result
+    let match_key = match_account.key();
+    let message = match_account.result_message(&match_key, &winner, end_reason);
+    require_signed_message(&ctx.accounts.instructions, &referee, &message)?;
+    
+    match_account.winner = Some(winner);
+    match_account.status = MatchStatus::Finished;
+    match_account.finished_at = Clock::get()?.unix_timestamp;
+    match_account.end_reason = Some(end_reason);
+    
+    msg!("Referee attested result. Winner: {} ({:?})", winner, end_reason);
+    
+    // Calculate amounts
+    let (fee_amount, payout_amount) = winner_payout(match_account)?;
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let vault = StakeVault::new(match_account, &system_program, &ctx.accounts.escrow, &ctx.accounts.token)?;
+    
+    // Only settle against the expected escrow balance
+    vault.sweep_surplus(match_account, &ctx.accounts.player_a)?;
+    
+    msg!("Fee: {} lamports", fee_amount);
+    msg!("Payout to winner: {} lamports", payout_amount);
+    
+    let fee_received = vault.pay_fee(&ctx.accounts.fee_vault.to_account_info(), fee_amount)?;
+    
+    vault.pay_player(&ctx.accounts.winner, payout_amount)?;
+    vault.close(&ctx.accounts.player_a)?;
+    
+    let paid = if winner == match_account.player_a {
+        [payout_amount, 0]
+    } else {
+        [0, payout_amount]
+    };
+    ctx.accounts.receipt.record(
+        match_key,
+        match_account,
+        MatchOutcome::Won,
+        paid,
+        fee_amount,
+        ctx.accounts.payer.key(),
+    )?;
+    
+    record_stake_fee(
+        &mut ctx.accounts.fee_vault,
+        *ctx.bumps.get("fee_vault").unwrap(),
+        ctx.accounts.token.token_fee_vault.as_mut(),
+        match_account,
+        fee_received,
+    )?;
+    
+    msg!("Attested settlement complete. Match account closed.");
+    
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Start (or restart) replaying the move transcript of a contested or disputed
+/// match from the initial position, as the first step towards `prove_checkmate`.
+#[derive(Accounts)]
+pub struct StartReplay<'info> {
+    #[account(
+        constraint = match_account.status == MatchStatus::Contested
+                  || match_account.status == MatchStatus::Disputed
+                  @ EscrowError::MatchNotDisputed,
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    #[account(
+        init_if_needed,
+        payer = prover,
+        space = GameReplay::LEN,
+        seeds = [b"replay", match_account.key().as_ref()],
+        bump
+    )]
+    pub replay: Account<'info, GameReplay>,
+    
+    #[account(
+        mut,
+        constraint = match_account.opponent_of(&prover.key()).is_some() @ EscrowError::NotAPlayer
+    )]
+    pub prover: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<StartReplay>) -> Result<()> {
+    let match_account = &ctx.accounts.match_account;
+    let match_key = match_account.key();
+    let prover = ctx.accounts.prover.key();
+    let replay = &mut ctx.accounts.replay;
+    
+    // A replay of the current game can only be restarted by whoever started it;
+    // one left over from an earlier game of this match can be taken over
+    require!(
+        replay.prover == Pubkey::default() || replay.prover == prover || !replay.is_current(match_account),
+        EscrowError::ReplayMismatch
+    );
+    
+    replay.match_account = match_key;
+    replay.prover = prover;
+    replay.bump = *ctx.bumps.get("replay").unwrap();
+    replay.reset(&match_key, match_account);
+    
+    msg!("Replay started for match {} by {}", match_key, prover);
+    
+    Ok(())
+}
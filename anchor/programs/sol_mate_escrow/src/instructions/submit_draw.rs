@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Propose that an active match ended in a draw (stalemate, repetition, agreement...).
+/// Goes through the same confirm/contest flow as `submit_result`.
+#[derive(Accounts)]
+pub struct SubmitDraw<'info> {
+    #[account(
+        mut,
+        constraint = match_account.status == MatchStatus::Active @ EscrowError::MatchNotActive,
+        constraint = !match_account.correspondence @ EscrowError::CorrespondenceMatch,
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(Config::PAUSE_RESULTS) @ EscrowError::Paused,
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        constraint = match_account.opponent_of(&submitter.key()).is_some()
+                  @ EscrowError::OnlyPlayersCanSubmit
+    )]
+    pub submitter: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SubmitDraw>) -> Result<()> {
+    let match_account = &mut ctx.accounts.match_account;
+    let clock = Clock::get()?;
+    
+    // Record the proposal
+    match_account.winner = None;
+    match_account.is_draw = true;
+    match_account.end_reason = Some(EndReason::Draw);
+    match_account.proposed_by = Some(ctx.accounts.submitter.key());
+    match_account.proposed_at = clock.unix_timestamp;
+    match_account.status = MatchStatus::ResultProposed;
+    
+    msg!("Draw proposed.");
+    msg!(
+        "Challenge window closes at: {}",
+        clock.unix_timestamp.saturating_add(match_account.challenge_window)
+    );
+    
+    Ok(())
+}
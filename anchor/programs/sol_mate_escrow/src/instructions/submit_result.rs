@@ -2,17 +2,28 @@ use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::*;
 
+/// Propose the winner of an active match.
+/// The opponent can confirm the result right away with `confirm_result` or
+/// contest it with `contest_result` until the challenge window closes.
 #[derive(Accounts)]
 pub struct SubmitResult<'info> {
     #[account(
         mut,
         constraint = match_account.status == MatchStatus::Active @ EscrowError::MatchNotActive,
+        constraint = !match_account.correspondence @ EscrowError::CorrespondenceMatch,
     )]
     pub match_account: Account<'info, Match>,
     
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(Config::PAUSE_RESULTS) @ EscrowError::Paused,
+    )]
+    pub config: Account<'info, Config>,
+    
     #[account(
         constraint = submitter.key() == match_account.player_a 
-                  || submitter.key() == match_account.player_b.unwrap() 
+                  || Some(submitter.key()) == match_account.player_b 
                   @ EscrowError::OnlyPlayersCanSubmit
     )]
     pub submitter: Signer<'info>,
@@ -20,6 +31,7 @@ pub struct SubmitResult<'info> {
 
 pub fn handler(ctx: Context<SubmitResult>, winner: Pubkey) -> Result<()> {
     let match_account = &mut ctx.accounts.match_account;
+    let clock = Clock::get()?;
     
     // Validate winner is one of the players
     let player_b = match_account.player_b.ok_or(EscrowError::MatchNotActive)?;
@@ -28,11 +40,19 @@ pub fn handler(ctx: Context<SubmitResult>, winner: Pubkey) -> Result<()> {
         EscrowError::InvalidWinner
     );
     
-    // Update match state
+    // Record the proposal
     match_account.winner = Some(winner);
-    match_account.status = MatchStatus::Finished;
+    match_account.is_draw = false;
+    match_account.end_reason = None;
+    match_account.proposed_by = Some(ctx.accounts.submitter.key());
+    match_account.proposed_at = clock.unix_timestamp;
+    match_account.status = MatchStatus::ResultProposed;
     
-    msg!("Match finished. Winner: {}", winner);
+    msg!("Result proposed. Winner: {}", winner);
+    msg!(
+        "Challenge window closes at: {}",
+        clock.unix_timestamp.saturating_add(match_account.challenge_window)
+    );
     
     Ok(())
 }
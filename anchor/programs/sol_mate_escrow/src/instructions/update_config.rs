@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, FeeSchedule, Role};
+use crate::errors::EscrowError;
+
+/// Parameters to change on the global config. `None` leaves a field untouched.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct UpdateConfigParams {
+    pub challenge_window: Option<i64>,
+    pub dispute_bond: Option<u64>,
+    pub draw_fee_bps: Option<u16>,
+    pub abandon_timeouts: Option<[i64; Config::MAX_STAKE_TIERS]>,
+    pub expiry_bounty: Option<u64>,
+    pub min_join_window: Option<i64>,
+    pub max_join_window: Option<i64>,
+    pub force_refund_grace: Option<i64>,
+    pub min_stake: Option<u64>,
+    pub max_stake: Option<u64>,
+    pub fee_schedule: Option<FeeSchedule>,
+    pub dispute_timeout: Option<i64>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
+    ctx.accounts.config.require_role(Role::Admin, &ctx.accounts.admin.key())?;
+    
+    let config = &mut ctx.accounts.config;
+    
+    if let Some(challenge_window) = params.challenge_window {
+        require!(
+            (Config::MIN_CHALLENGE_WINDOW..=Config::MAX_CHALLENGE_WINDOW).contains(&challenge_window),
+            EscrowError::InvalidChallengeWindow
+        );
+        config.challenge_window = challenge_window;
+        msg!("Challenge window set to {}s", challenge_window);
+    }
+    
+    // A bond, if any, must on its own keep a token match's escrow PDA rent-exempt
+    if let Some(dispute_bond) = params.dispute_bond {
+        require!(
+            dispute_bond == 0 || dispute_bond >= Rent::get()?.minimum_balance(0),
+            EscrowError::InvalidDisputeBond
+        );
+        config.dispute_bond = dispute_bond;
+        msg!("Dispute bond set to {} lamports", dispute_bond);
+    }
+    
+    if let Some(draw_fee_bps) = params.draw_fee_bps {
+        require!(draw_fee_bps <= Config::MAX_BPS, EscrowError::InvalidFeeBps);
+        config.draw_fee_bps = draw_fee_bps;
+        msg!("Draw fee set to {} bps", draw_fee_bps);
+    }
+    
+    if let Some(abandon_timeouts) = params.abandon_timeouts {
+        require!(
+            abandon_timeouts
+                .iter()
+                .all(|timeout| (Config::MIN_ABANDON_TIMEOUT..=Config::MAX_ABANDON_TIMEOUT).contains(timeout)),
+            EscrowError::InvalidAbandonTimeout
+        );
+        config.abandon_timeouts = abandon_timeouts;
+        msg!("Abandon timeouts set to {:?}", abandon_timeouts);
+    }
+    
+    if let Some(expiry_bounty) = params.expiry_bounty {
+        require!(expiry_bounty <= Config::MAX_EXPIRY_BOUNTY, EscrowError::InvalidExpiryBounty);
+        config.expiry_bounty = expiry_bounty;
+        msg!("Expiry bounty set to {} lamports", expiry_bounty);
+    }
+    
+    if params.min_join_window.is_some() || params.max_join_window.is_some() {
+        let min_join_window = params.min_join_window.unwrap_or(config.min_join_window);
+        let max_join_window = params.max_join_window.unwrap_or(config.max_join_window);
+        require!(
+            min_join_window >= 0 && min_join_window <= max_join_window,
+            EscrowError::InvalidJoinWindow
+        );
+        config.min_join_window = min_join_window;
+        config.max_join_window = max_join_window;
+        msg!("Join window set to {}s - {}s", min_join_window, max_join_window);
+    }
+    
+    if let Some(force_refund_grace) = params.force_refund_grace {
+        require!(
+            (Config::MIN_FORCE_REFUND_GRACE..=Config::MAX_FORCE_REFUND_GRACE).contains(&force_refund_grace),
+            EscrowError::InvalidForceRefundGrace
+        );
+        config.force_refund_grace = force_refund_grace;
+        msg!("Force refund grace set to {}s", force_refund_grace);
+    }
+    
+    if params.min_stake.is_some() || params.max_stake.is_some() {
+        let min_stake = params.min_stake.unwrap_or(config.min_stake);
+        let max_stake = params.max_stake.unwrap_or(config.max_stake);
+        require!(
+            min_stake >= Config::MIN_STAKE_FLOOR && min_stake <= max_stake,
+            EscrowError::InvalidStakeBounds
+        );
+        config.min_stake = min_stake;
+        config.max_stake = max_stake;
+        msg!("Stake bounds set to {} - {} lamports", min_stake, max_stake);
+    }
+    
+    // Only matches created (or re-tiered) from now on pick up the new schedule
+    if let Some(fee_schedule) = params.fee_schedule {
+        require!(fee_schedule.is_valid(), EscrowError::InvalidFeeSchedule);
+        config.fee_schedule = fee_schedule;
+        msg!("Fee schedule updated. Fee bounds: {} - {} lamports", fee_schedule.min_fee, fee_schedule.max_fee);
+    }
+    
+    // Disputes already open keep the deadline they were opened with
+    if let Some(dispute_timeout) = params.dispute_timeout {
+        require!(
+            (Config::MIN_DISPUTE_TIMEOUT..=Config::MAX_DISPUTE_TIMEOUT).contains(&dispute_timeout),
+            EscrowError::InvalidDisputeTimeout
+        );
+        config.dispute_timeout = dispute_timeout;
+        msg!("Dispute timeout set to {}s", dispute_timeout);
+    }
+    
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::payout::StakeVault;
+use crate::token::*;
+
+/// Changes to an open match. `None` leaves a field untouched.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct UpdateOpenMatchParams {
+    pub join_deadline: Option<i64>,
+    pub stake_tier: Option<u8>,
+    pub stake_amount: Option<u64>,
+    /// Player allowed to join; the default pubkey opens the match to anyone
+    pub invitee: Option<Pubkey>,
+}
+
+/// Let the host change an open match before anyone joins.
+/// Changing the stake amount tops up or refunds the difference in escrow.
+#[derive(Accounts)]
+pub struct UpdateOpenMatch<'info> {
+    #[account(
+        mut,
+        constraint = match_account.status == MatchStatus::Open @ EscrowError::MatchNotOpen,
+        constraint = match_account.player_b.is_none() @ EscrowError::MatchAlreadyJoined,
+    )]
+    pub match_account: Account<'info, Match>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow", match_account.key().as_ref()],
+        bump = match_account.escrow_bump
+    )]
+    /// CHECK: PDA for holding escrow funds
+    pub escrow: AccountInfo<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(Config::PAUSE_CREATE) @ EscrowError::Paused,
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        constraint = player_a.key() == match_account.player_a @ EscrowError::OnlyCreator
+    )]
+    pub player_a: Signer<'info>,
+    
+    pub token: TokenAccounts<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<UpdateOpenMatch>, params: UpdateOpenMatchParams) -> Result<()> {
+    let clock = Clock::get()?;
+    let config = &ctx.accounts.config;
+    let match_account = &mut ctx.accounts.match_account;
+    
+    if let Some(join_deadline) = params.join_deadline {
+        require!(
+            config.join_deadline_valid(join_deadline, clock.unix_timestamp),
+            EscrowError::InvalidTimestamp
+        );
+        match_account.join_deadline = join_deadline;
+        msg!("Join deadline set to {}", join_deadline);
+    }
+    
+    if let Some(stake_tier) = params.stake_tier {
+        require!(config.stake_tier(stake_tier).is_some(), EscrowError::InvalidStakeTier);
+        match_account.stake_tier = stake_tier;
+        match_account.abandon_timeout = config.abandon_timeout(stake_tier);
+        match_account.snapshot_fees(&config.fee_schedule);
+        msg!("Stake tier set to {}", stake_tier);
+    }
+    
+    if let Some(new_stake) = params.stake_amount {
+        // Zero re-prices a SOL match to its tier's current preset
+        let new_stake = if new_stake == 0 && !match_account.is_token_match() {
+            config
+                .stake_tier(match_account.stake_tier)
+                .ok_or(EscrowError::InvalidStakeTier)?
+                .lamports
+        } else {
+            new_stake
+        };
+        // Token matches take their bounds from the mint's fee vault
+        let stake_valid = if match_account.is_token_match() {
+            let token_fee_vault = ctx.accounts.token.token_fee_vault.as_ref().ok_or(EscrowError::MissingTokenAccounts)?;
+            require!(token_fee_vault.mint == match_account.mint, EscrowError::InvalidMint);
+            token_fee_vault.stake_valid(new_stake)
+        } else {
+            config.stake_valid(new_stake)
+        };
+        require!(stake_valid, EscrowError::InvalidStakeAmount);
+        let old_stake = match_account.stake_amount_lamports();
+        
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let player_a = ctx.accounts.player_a.to_account_info();
+        let vault = StakeVault::new(match_account, &system_program, &ctx.accounts.escrow, &ctx.accounts.token)?;
+        if new_stake > old_stake {
+            vault.deposit(&player_a, new_stake - old_stake)?;
+        } else if new_stake < old_stake {
+            vault.pay_player(&player_a, old_stake - new_stake)?;
+        }
+        match_account.stake_amount = new_stake;
+        
+        msg!("Stake set to {}", new_stake);
+    }
+    
+    if let Some(invitee) = params.invitee {
+        match_account.invitee = if invitee == Pubkey::default() {
+            None
+        } else {
+            require!(invitee != match_account.player_a, EscrowError::CannotJoinOwnMatch);
+            Some(invitee)
+        };
+        msg!("Invitee set to {}", invitee);
+    }
+    
+    Ok(())
+}
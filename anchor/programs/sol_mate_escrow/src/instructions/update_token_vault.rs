@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Role, TokenFeeVault};
+use crate::errors::EscrowError;
+
+/// Change the stake bounds of a whitelisted mint. Only the platform admin can call this.
+#[derive(Accounts)]
+pub struct UpdateTokenVault<'info> {
+    #[account(
+        mut,
+        seeds = [b"fee_vault", token_fee_vault.mint.as_ref()],
+        bump = token_fee_vault.bump,
+    )]
+    pub token_fee_vault: Account<'info, TokenFeeVault>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UpdateTokenVault>, min_stake: u64, max_stake: u64) -> Result<()> {
+    ctx.accounts.config.require_role(Role::Admin, &ctx.accounts.admin.key())?;
+    require!(min_stake > 0 && min_stake <= max_stake, EscrowError::InvalidStakeBounds);
+    
+    let token_fee_vault = &mut ctx.accounts.token_fee_vault;
+    token_fee_vault.min_stake = min_stake;
+    token_fee_vault.max_stake = max_stake;
+    
+    msg!("Stake bounds for mint {} set to {} - {}", token_fee_vault.mint, min_stake, max_stake);
+    
+    Ok(())
+}
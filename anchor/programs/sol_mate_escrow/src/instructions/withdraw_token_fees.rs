@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Role, TokenFeeVault};
+use crate::errors::EscrowError;
+use crate::payout::transfer_token_fees;
+use crate::token::token_balance;
+
+/// Withdraw platform fees collected in a token to a token account of the fee withdrawer.
+#[derive(Accounts)]
+pub struct WithdrawTokenFees<'info> {
+    #[account(
+        seeds = [b"fee_vault", token_fee_vault.mint.as_ref()],
+        bump = token_fee_vault.bump,
+    )]
+    pub token_fee_vault: Account<'info, TokenFeeVault>,
+    
+    /// CHECK: Token account of the fee vault, checked by seeds
+    #[account(
+        mut,
+        seeds = [b"fee_vault_token", token_fee_vault.mint.as_ref()],
+        bump = token_fee_vault.token_account_bump,
+    )]
+    pub fee_vault_token: AccountInfo<'info>,
+    
+    /// CHECK: Fee vault's mint
+    #[account(address = token_fee_vault.mint @ EscrowError::InvalidMint)]
+    pub mint: AccountInfo<'info>,
+    
+    /// CHECK: Fee withdrawer's token account of the mint, checked in handler
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub fee_withdrawer: Signer<'info>,
+    
+    /// CHECK: SPL Token or Token-2022 program, checked in handler
+    pub token_program: AccountInfo<'info>,
+}
+
+pub fn handler(ctx: Context<WithdrawTokenFees>, amount: u64) -> Result<()> {
+    // Verify the signer holds the fee withdrawer role
+    ctx.accounts.config.require_role(Role::FeeWithdrawer, &ctx.accounts.fee_withdrawer.key())?;
+    require!(!ctx.accounts.config.fee_multisig.is_enabled(), EscrowError::MultisigRequired);
+    
+    // Fees only go to a token account of the fee withdrawer
+    token_balance(
+        &ctx.accounts.destination,
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_fee_vault.mint,
+        &ctx.accounts.fee_withdrawer.key(),
+    )?;
+    transfer_token_fees(
+        &ctx.accounts.token_fee_vault,
+        &ctx.accounts.fee_vault_token,
+        &ctx.accounts.mint,
+        &ctx.accounts.destination,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+    
+    Ok(())
+}
@@ -3,10 +3,21 @@ use anchor_lang::prelude::*;
 pub mod instructions;
 pub mod state;
 pub mod errors;
+pub mod events;
+pub mod ed25519;
+pub mod chess;
+pub mod payout;
+pub mod token;
 
 // Re-export everything from instructions for Anchor macros
 pub use instructions::*;
+pub use state::{DisputeResolution, EndReason, Role};
 
+// `devnet` builds deploy under their own program id, so a devnet build can
+// never be mistaken for the live mainnet program
+#[cfg(feature = "devnet")]
+declare_id!("6aeWAMfGRCJikhTT9qnXmiXj8ZBZ7Sg9TbZtdk2EVeVU");
+#[cfg(not(feature = "devnet"))]
 declare_id!("H1Sn4JQvsZFx7HreZaQn4Poa3hkoS9iGnTwrtN2knrKV");
 
 #[program]
@@ -18,26 +29,129 @@ pub mod sol_mate_escrow {
         stake_tier: u8,
         seed: u64,
         join_deadline: i64,
+        stake_amount: u64,
     ) -> Result<()> {
-        crate::instructions::create_match::handler(ctx, stake_tier, seed, join_deadline)
+        crate::instructions::create_match::handler(ctx, stake_tier, seed, join_deadline, stake_amount)
+    }
+
+    pub fn update_open_match(
+        ctx: Context<UpdateOpenMatch>,
+        params: UpdateOpenMatchParams,
+    ) -> Result<()> {
+        crate::instructions::update_open_match::handler(ctx, params)
+    }
+
+    pub fn enable_correspondence(
+        ctx: Context<EnableCorrespondence>,
+        seconds_per_move: i64,
+        host_plays_white: bool,
+    ) -> Result<()> {
+        crate::instructions::enable_correspondence::handler(ctx, seconds_per_move, host_plays_white)
     }
 
     pub fn join_match(ctx: Context<JoinMatch>) -> Result<()> {
         crate::instructions::join_match::handler(ctx)
     }
 
+    pub fn make_move(ctx: Context<MakeMove>, raw_move: u16) -> Result<()> {
+        crate::instructions::make_move::handler(ctx, raw_move)
+    }
+
+    pub fn claim_win_on_time(ctx: Context<ClaimWinOnTime>) -> Result<()> {
+        crate::instructions::claim_win_on_time::handler(ctx)
+    }
+
+    pub fn close_game(ctx: Context<CloseGame>) -> Result<()> {
+        crate::instructions::close_game::handler(ctx)
+    }
+
     pub fn submit_result(ctx: Context<SubmitResult>, winner: Pubkey) -> Result<()> {
         crate::instructions::submit_result::handler(ctx, winner)
     }
 
+    pub fn submit_draw(ctx: Context<SubmitDraw>) -> Result<()> {
+        crate::instructions::submit_draw::handler(ctx)
+    }
+
+    pub fn resign(ctx: Context<Resign>) -> Result<()> {
+        crate::instructions::resign::handler(ctx)
+    }
+
+    pub fn confirm_result(ctx: Context<ConfirmResult>) -> Result<()> {
+        crate::instructions::confirm_result::handler(ctx)
+    }
+
+    pub fn contest_result(ctx: Context<ContestResult>) -> Result<()> {
+        crate::instructions::contest_result::handler(ctx)
+    }
+
+    pub fn settle_with_attestation(
+        ctx: Context<SettleWithAttestation>,
+        winner: Pubkey,
+        end_reason: EndReason,
+    ) -> Result<()> {
+        crate::instructions::settle_with_attestation::handler(ctx, winner, end_reason)
+    }
+
+    pub fn settle_mutual(
+        ctx: Context<SettleMutual>,
+        winner: Pubkey,
+        end_reason: EndReason,
+    ) -> Result<()> {
+        crate::instructions::settle_mutual::handler(ctx, winner, end_reason)
+    }
+
+    pub fn open_dispute(ctx: Context<OpenDispute>) -> Result<()> {
+        crate::instructions::open_dispute::handler(ctx)
+    }
+
+    pub fn resolve_dispute(
+        ctx: Context<ResolveDispute>,
+        resolution: DisputeResolution,
+    ) -> Result<()> {
+        crate::instructions::resolve_dispute::handler(ctx, resolution)
+    }
+
+    pub fn start_replay(ctx: Context<StartReplay>) -> Result<()> {
+        crate::instructions::start_replay::handler(ctx)
+    }
+
+    pub fn replay_moves(ctx: Context<ReplayMoves>, moves: Vec<u16>) -> Result<()> {
+        crate::instructions::replay_moves::handler(ctx, moves)
+    }
+
+    pub fn prove_checkmate(ctx: Context<ProveCheckmate>) -> Result<()> {
+        crate::instructions::prove_checkmate::handler(ctx)
+    }
+
     pub fn confirm_payout(ctx: Context<ConfirmPayout>) -> Result<()> {
         crate::instructions::confirm_payout::handler(ctx)
     }
 
+    pub fn settle_draw(ctx: Context<SettleDraw>) -> Result<()> {
+        crate::instructions::settle_draw::handler(ctx)
+    }
+
+    pub fn rematch(ctx: Context<Rematch>) -> Result<()> {
+        crate::instructions::rematch::handler(ctx)
+    }
+
+    pub fn close_receipt(ctx: Context<CloseReceipt>) -> Result<()> {
+        crate::instructions::close_receipt::handler(ctx)
+    }
+
     pub fn cancel_match(ctx: Context<CancelMatch>) -> Result<()> {
         crate::instructions::cancel_match::handler(ctx)
     }
 
+    pub fn expire_match<'info>(ctx: Context<'_, '_, '_, 'info, ExpireMatch<'info>>) -> Result<()> {
+        crate::instructions::expire_match::handler(ctx)
+    }
+
+    pub fn abort_match(ctx: Context<AbortMatch>) -> Result<()> {
+        crate::instructions::abort_match::handler(ctx)
+    }
+
     pub fn abandon_match(ctx: Context<AbandonMatch>) -> Result<()> {
         crate::instructions::abandon_match::handler(ctx)
     }
@@ -49,4 +163,86 @@ pub mod sol_mate_escrow {
     pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
         crate::instructions::withdraw_fees::handler(ctx, amount)
     }
+
+    pub fn initialize_token_vault(
+        ctx: Context<InitializeTokenVault>,
+        min_stake: u64,
+        max_stake: u64,
+    ) -> Result<()> {
+        crate::instructions::initialize_token_vault::handler(ctx, min_stake, max_stake)
+    }
+
+    pub fn update_token_vault(
+        ctx: Context<UpdateTokenVault>,
+        min_stake: u64,
+        max_stake: u64,
+    ) -> Result<()> {
+        crate::instructions::update_token_vault::handler(ctx, min_stake, max_stake)
+    }
+
+    pub fn withdraw_token_fees(ctx: Context<WithdrawTokenFees>, amount: u64) -> Result<()> {
+        crate::instructions::withdraw_token_fees::handler(ctx, amount)
+    }
+
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        crate::instructions::initialize_config::handler(ctx)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
+        crate::instructions::update_config::handler(ctx, params)
+    }
+
+    pub fn set_stake_tier(
+        ctx: Context<SetStakeTier>,
+        stake_tier: u8,
+        lamports: u64,
+        enabled: bool,
+    ) -> Result<()> {
+        crate::instructions::set_stake_tier::handler(ctx, stake_tier, lamports, enabled)
+    }
+
+    pub fn propose_role(ctx: Context<ProposeRole>, role: Role, nominee: Pubkey) -> Result<()> {
+        crate::instructions::propose_role::handler(ctx, role, nominee)
+    }
+
+    pub fn accept_role(ctx: Context<AcceptRole>, role: Role) -> Result<()> {
+        crate::instructions::accept_role::handler(ctx, role)
+    }
+
+    pub fn renounce_role(ctx: Context<RenounceRole>, role: Role) -> Result<()> {
+        crate::instructions::renounce_role::handler(ctx, role)
+    }
+
+    pub fn set_fee_multisig(ctx: Context<SetFeeMultisig>, params: FeeMultisigParams) -> Result<()> {
+        crate::instructions::set_fee_multisig::handler(ctx, params)
+    }
+
+    pub fn propose_withdrawal(
+        ctx: Context<ProposeWithdrawal>,
+        mint: Pubkey,
+        amount: u64,
+        recipient: Pubkey,
+    ) -> Result<()> {
+        crate::instructions::propose_withdrawal::handler(ctx, mint, amount, recipient)
+    }
+
+    pub fn approve_withdrawal(ctx: Context<ApproveWithdrawal>) -> Result<()> {
+        crate::instructions::approve_withdrawal::handler(ctx)
+    }
+
+    pub fn execute_withdrawal(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+        crate::instructions::execute_withdrawal::handler(ctx)
+    }
+
+    pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>) -> Result<()> {
+        crate::instructions::cancel_withdrawal::handler(ctx)
+    }
+
+    pub fn set_pause(ctx: Context<SetPause>, paused: u8) -> Result<()> {
+        crate::instructions::set_pause::handler(ctx, paused)
+    }
+
+    pub fn expire_dispute(ctx: Context<ExpireDispute>) -> Result<()> {
+        crate::instructions::expire_dispute::handler(ctx)
+    }
 }
@@ -0,0 +1,562 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::{FeeVault, Match, TokenFeeVault};
+use crate::errors::EscrowError;
+use crate::token::{check_token_program, close_token_account, gross_amount, mint_decimals, token_balance, TokenAccounts};
+
+/// `bps` basis points of `amount`, rounded down.
+fn bps_of(amount: u64, bps: u16) -> Result<u64> {
+    (amount as u128)
+        .checked_mul(bps as u128)
+        .and_then(|fee| fee.checked_div(10_000))
+        .and_then(|fee| u64::try_from(fee).ok())
+        .ok_or(error!(EscrowError::ArithmeticOverflow))
+}
+
+/// Platform fee charged on each stake of a decided or split match, using the fee
+/// schedule snapshotted into the match: the host's stake at the maker rate and the
+/// joiner's stake at the taker rate. When the min/max bounds change the total, it is
+/// spread over the two stakes in proportion to their own fees.
+/// Returns `[host_fee, joiner_fee]`.
+pub fn stake_fees(match_account: &Match) -> Result<[u64; 2]> {
+    let stake_amount = match_account.stake_amount_lamports();
+    let total_pot = stake_amount
+        .checked_mul(2)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    
+    let fees = [
+        bps_of(stake_amount, match_account.maker_fee_bps)?,
+        bps_of(stake_amount, match_account.taker_fee_bps)?,
+    ];
+    let unbounded = fees[0]
+        .checked_add(fees[1])
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    let mut total = unbounded.max(match_account.min_fee);
+    if match_account.max_fee > 0 {
+        total = total.min(match_account.max_fee);
+    }
+    let total = total.min(total_pot);
+    if total == unbounded {
+        return Ok(fees);
+    }
+    
+    // Neither stake can be charged more than it holds
+    let host_fee = if unbounded == 0 {
+        total - total / 2
+    } else {
+        (total as u128 * fees[0] as u128 / unbounded as u128) as u64
+    };
+    let host_fee = host_fee.clamp(total.saturating_sub(stake_amount), stake_amount);
+    
+    Ok([host_fee, total - host_fee])
+}
+
+/// Split the pot of a decided match into the platform fee and the winner's payout.
+/// Each stake pays its own side's fee, see `stake_fees`.
+/// Returns `(fee_amount, payout_amount)`.
+pub fn winner_payout(match_account: &Match) -> Result<(u64, u64)> {
+    let total_pot = match_account
+        .stake_amount_lamports()
+        .checked_mul(2)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    let [host_fee, joiner_fee] = stake_fees(match_account)?;
+    let fee_amount = host_fee + joiner_fee;
+    
+    let payout_amount = total_pot
+        .checked_sub(fee_amount)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    
+    Ok((fee_amount, payout_amount))
+}
+
+/// Split one stake of a drawn match into the draw fee and the amount returned to its player.
+/// Returns `(fee_amount, refund_amount)` per player.
+pub fn draw_refund(stake_amount: u64, draw_fee_bps: u16) -> Result<(u64, u64)> {
+    let fee_amount = bps_of(stake_amount, draw_fee_bps)?;
+    
+    let refund_amount = stake_amount
+        .checked_sub(fee_amount)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    
+    Ok((fee_amount, refund_amount))
+}
+
+/// Transfer lamports out of a match escrow PDA, signing with its seeds.
+pub fn transfer_from_escrow<'info>(
+    system_program: &AccountInfo<'info>,
+    escrow: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    match_key: &Pubkey,
+    escrow_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let escrow_seeds: &[&[u8]] = &[
+        b"escrow",
+        match_key.as_ref(),
+        &[escrow_bump],
+    ];
+    
+    transfer(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Transfer {
+                from: escrow.clone(),
+                to: to.clone(),
+            },
+            &[escrow_seeds],
+        ),
+        amount,
+    )
+}
+
+/// Where a match's stakes are held: lamports in the escrow PDA for SOL matches,
+/// or a token account owned by the escrow PDA for matches staked in a mint.
+/// Deposits, payouts and refunds go through here so every instruction handles both.
+pub struct StakeVault<'a, 'info> {
+    system_program: &'a AccountInfo<'info>,
+    escrow: &'a AccountInfo<'info>,
+    match_key: Pubkey,
+    escrow_bump: u8,
+    player_a: Pubkey,
+    token: Option<TokenStake<'a, 'info>>,
+}
+
+/// Token side of a `StakeVault`.
+struct TokenStake<'a, 'info> {
+    program: &'a AccountInfo<'info>,
+    mint: &'a AccountInfo<'info>,
+    escrow_token: &'a AccountInfo<'info>,
+    decimals: u8,
+    fee_vault: Option<Pubkey>,
+    fee_vault_token: Option<&'a AccountInfo<'info>>,
+    player_tokens: [Option<&'a AccountInfo<'info>>; 2],
+}
+
+impl<'a, 'info> StakeVault<'a, 'info> {
+    /// Validate the token accounts of a token match; SOL matches ignore them.
+    pub fn new(
+        match_account: &Account<'info, Match>,
+        system_program: &'a AccountInfo<'info>,
+        escrow: &'a AccountInfo<'info>,
+        accounts: &'a TokenAccounts<'info>,
+    ) -> Result<Self> {
+        let match_key = match_account.key();
+        let token = if match_account.is_token_match() {
+            let program = accounts.token_program.as_deref().ok_or(EscrowError::MissingTokenAccounts)?;
+            let mint = accounts.mint.as_deref().ok_or(EscrowError::MissingTokenAccounts)?;
+            let escrow_token = accounts.token_escrow.as_deref().ok_or(EscrowError::MissingTokenAccounts)?;
+            
+            check_token_program(program)?;
+            require!(*mint.key == match_account.mint, EscrowError::InvalidMint);
+            let expected_escrow_token = Pubkey::create_program_address(
+                &[b"token_escrow", match_key.as_ref(), &[match_account.token_escrow_bump]],
+                &crate::ID,
+            )
+            .map_err(|_| EscrowError::InvalidTokenAccount)?;
+            require!(*escrow_token.key == expected_escrow_token, EscrowError::InvalidTokenAccount);
+            
+            let fee_vault = match &accounts.token_fee_vault {
+                Some(fee_vault) => {
+                    require!(fee_vault.mint == match_account.mint, EscrowError::InvalidMint);
+                    Some(fee_vault.key())
+                }
+                None => None,
+            };
+            
+            Some(TokenStake {
+                program,
+                mint,
+                escrow_token,
+                decimals: mint_decimals(mint, program)?,
+                fee_vault,
+                fee_vault_token: accounts.fee_vault_token.as_deref(),
+                player_tokens: [accounts.player_a_token.as_deref(), accounts.player_b_token.as_deref()],
+            })
+        } else {
+            None
+        };
+        
+        Ok(Self {
+            system_program,
+            escrow,
+            match_key,
+            escrow_bump: match_account.escrow_bump,
+            player_a: match_account.player_a,
+            token,
+        })
+    }
+    
+    /// Move `amount` of stake from `player` (who must sign) into escrow. For token
+    /// matches the player also pays any transfer fee, so exactly `amount` arrives.
+    pub fn deposit(&self, player: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        let Some(token) = &self.token else {
+            return transfer(
+                CpiContext::new(
+                    self.system_program.clone(),
+                    Transfer {
+                        from: player.clone(),
+                        to: self.escrow.clone(),
+                    },
+                ),
+                amount,
+            );
+        };
+        
+        let from = token.player_token(player.key, &self.player_a)?;
+        token_balance(from, token.program, token.mint.key, player.key)?;
+        let before = self.token_balance(token)?;
+        
+        let ix = spl_token_2022::instruction::transfer_checked(
+            token.program.key,
+            from.key,
+            token.mint.key,
+            token.escrow_token.key,
+            player.key,
+            &[],
+            gross_amount(token.mint, amount)?,
+            token.decimals,
+        )?;
+        invoke(
+            &ix,
+            &[from.clone(), token.mint.clone(), token.escrow_token.clone(), player.clone(), token.program.clone()],
+        )?;
+        
+        let received = self.token_balance(token)?.checked_sub(before);
+        require!(received == Some(amount), EscrowError::TokenAmountMismatch);
+        
+        Ok(())
+    }
+    
+    /// Pay `amount` of stake out of escrow to `player`, or to their token account for token matches.
+    pub fn pay_player(&self, player: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        match &self.token {
+            None => self.pay_lamports(player, amount),
+            Some(token) => {
+                let to = token.player_token(player.key, &self.player_a)?;
+                token_balance(to, token.program, token.mint.key, player.key)?;
+                self.pay_tokens(token, to, amount)
+            }
+        }
+    }
+    
+    /// Pay `amount` of platform fee out of escrow to the SOL fee vault, or to the
+    /// mint's fee vault for token matches. Returns the amount the fee vault actually
+    /// received, which is less than `amount` if the mint charges a transfer fee.
+    pub fn pay_fee(&self, fee_vault: &AccountInfo<'info>, amount: u64) -> Result<u64> {
+        if amount == 0 {
+            return Ok(0);
+        }
+        match &self.token {
+            None => {
+                self.pay_lamports(fee_vault, amount)?;
+                Ok(amount)
+            }
+            Some(token) => {
+                let vault = token.fee_vault.ok_or(EscrowError::MissingTokenAccounts)?;
+                let to = token.fee_vault_token.ok_or(EscrowError::MissingTokenAccounts)?;
+                let balance_before = token_balance(to, token.program, token.mint.key, &vault)?;
+                self.pay_tokens(token, to, amount)?;
+                let balance_after = token_balance(to, token.program, token.mint.key, &vault)?;
+                Ok(balance_after
+                    .checked_sub(balance_before)
+                    .ok_or(EscrowError::ArithmeticOverflow)?)
+            }
+        }
+    }
+    
+    /// Check the escrow holds at least what the match owes out of it, and send any
+    /// surplus (e.g. unsolicited transfers) to player A, who funded the escrow.
+    /// Call before paying out so settlements only ever move the expected amounts.
+    pub fn sweep_surplus(&self, match_account: &Match, player_a: &AccountInfo<'info>) -> Result<()> {
+        let stakes = match_account.stake_liabilities()?;
+        
+        // Lamports cover the dispute bond, plus the stakes of a SOL match
+        let lamport_liabilities = match self.token {
+            None => stakes.checked_add(match_account.dispute_bond),
+            Some(_) => Some(match_account.dispute_bond),
+        }
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+        let lamport_surplus = self
+            .escrow
+            .lamports()
+            .checked_sub(lamport_liabilities)
+            .ok_or(EscrowError::EscrowBalanceMismatch)?;
+        if lamport_surplus > 0 {
+            msg!("Returning {} lamports of escrow surplus to player A", lamport_surplus);
+            self.pay_lamports(player_a, lamport_surplus)?;
+        }
+        
+        if let Some(token) = &self.token {
+            let token_surplus = self
+                .token_balance(token)?
+                .checked_sub(stakes)
+                .ok_or(EscrowError::EscrowBalanceMismatch)?;
+            if token_surplus > 0 {
+                msg!("Returning {} tokens of escrow surplus to player A", token_surplus);
+                self.pay_player(player_a, token_surplus)?;
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Close the emptied token escrow of a token match, returning its rent to `rent_to`.
+    pub fn close(&self, rent_to: &AccountInfo<'info>) -> Result<()> {
+        if let Some(token) = &self.token {
+            close_token_account(
+                token.program,
+                token.mint,
+                token.escrow_token,
+                rent_to,
+                self.escrow,
+                &[b"escrow", self.match_key.as_ref(), &[self.escrow_bump]],
+            )?;
+        }
+        
+        Ok(())
+    }
+    
+    fn pay_lamports(&self, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        transfer_from_escrow(
+            self.system_program,
+            self.escrow,
+            to,
+            &self.match_key,
+            self.escrow_bump,
+            amount,
+        )
+    }
+    
+    fn pay_tokens(&self, token: &TokenStake<'a, 'info>, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        let ix = spl_token_2022::instruction::transfer_checked(
+            token.program.key,
+            token.escrow_token.key,
+            token.mint.key,
+            to.key,
+            self.escrow.key,
+            &[],
+            amount,
+            token.decimals,
+        )?;
+        invoke_signed(
+            &ix,
+            &[token.escrow_token.clone(), token.mint.clone(), to.clone(), self.escrow.clone(), token.program.clone()],
+            &[&[b"escrow", self.match_key.as_ref(), &[self.escrow_bump]]],
+        )?;
+        
+        Ok(())
+    }
+    
+    fn token_balance(&self, token: &TokenStake<'a, 'info>) -> Result<u64> {
+        token_balance(token.escrow_token, token.program, token.mint.key, self.escrow.key)
+    }
+}
+
+impl<'a, 'info> TokenStake<'a, 'info> {
+    /// Token account passed for `player`.
+    fn player_token(&self, player: &Pubkey, player_a: &Pubkey) -> Result<&'a AccountInfo<'info>> {
+        let index = if player == player_a { 0 } else { 1 };
+        Ok(self.player_tokens[index].ok_or(EscrowError::MissingTokenAccounts)?)
+    }
+}
+
+/// Record collected fees on the fee vault, setting its bump on first use.
+pub fn record_fee(fee_vault: &mut FeeVault, bump: u8, fee_amount: u64) -> Result<()> {
+    if fee_vault.total_collected == 0 {
+        fee_vault.bump = bump;
+    }
+    fee_vault.total_collected = fee_vault
+        .total_collected
+        .checked_add(fee_amount)
+        .ok_or(EscrowError::ArithmeticOverflow)?;
+    
+    Ok(())
+}
+
+/// Record fees taken from the stakes on the vault `StakeVault::pay_fee` paid them into.
+/// `fee_amount` is what the vault received, as returned by `pay_fee`.
+pub fn record_stake_fee(
+    fee_vault: &mut FeeVault,
+    bump: u8,
+    token_fee_vault: Option<&mut Account<TokenFeeVault>>,
+    match_account: &Match,
+    fee_amount: u64,
+) -> Result<()> {
+    if !match_account.is_token_match() {
+        return record_fee(fee_vault, bump, fee_amount);
+    }
+    
+    // Still sets the SOL vault's bump if this instruction just created it
+    record_fee(fee_vault, bump, 0)?;
+    if fee_amount > 0 {
+        let token_fee_vault = token_fee_vault.ok_or(EscrowError::MissingTokenAccounts)?;
+        token_fee_vault.total_collected = token_fee_vault
+            .total_collected
+            .checked_add(fee_amount)
+            .ok_or(EscrowError::ArithmeticOverflow)?;
+    }
+    
+    Ok(())
+}
+
+/// Move `amount` lamports out of the SOL fee vault to `to`; 0 withdraws everything
+/// above the vault's rent-exempt minimum. Returns the amount withdrawn.
+pub fn transfer_sol_fees(fee_vault: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<u64> {
+    // Get current balance (excluding rent-exempt minimum)
+    let rent = Rent::get()?;
+    let min_balance = rent.minimum_balance(FeeVault::LEN);
+    let available_balance = fee_vault.lamports()
+        .checked_sub(min_balance)
+        .ok_or(EscrowError::InsufficientFunds)?;
+    
+    // Determine withdrawal amount
+    let withdraw_amount = if amount == 0 {
+        available_balance // Withdraw all if amount is 0
+    } else {
+        require!(amount <= available_balance, EscrowError::InsufficientFunds);
+        amount
+    };
+    
+    require!(withdraw_amount > 0, EscrowError::InsufficientFunds);
+    
+    msg!("Withdrawing {} lamports to {}", withdraw_amount, to.key());
+    msg!("Fee vault balance before: {}", fee_vault.lamports());
+    
+    // Transfer from fee vault PDA
+    **fee_vault.try_borrow_mut_lamports()? -= withdraw_amount;
+    **to.try_borrow_mut_lamports()? += withdraw_amount;
+    
+    msg!("Fee vault balance after: {}", fee_vault.lamports());
+    
+    Ok(withdraw_amount)
+}
+
+/// Move `amount` tokens out of a mint's fee vault token account to `destination`,
+/// signed by the token fee vault PDA; 0 withdraws everything. Returns the amount withdrawn.
+pub fn transfer_token_fees<'info>(
+    token_fee_vault: &Account<'info, TokenFeeVault>,
+    fee_vault_token: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<u64> {
+    check_token_program(token_program)?;
+    require!(*mint.key == token_fee_vault.mint, EscrowError::InvalidMint);
+    let available_balance = token_balance(fee_vault_token, token_program, mint.key, &token_fee_vault.key())?;
+    
+    // Determine withdrawal amount
+    let withdraw_amount = if amount == 0 {
+        available_balance // Withdraw all if amount is 0
+    } else {
+        require!(amount <= available_balance, EscrowError::InsufficientFunds);
+        amount
+    };
+    
+    require!(withdraw_amount > 0, EscrowError::InsufficientFunds);
+    
+    msg!("Withdrawing {} of mint {} to {}", withdraw_amount, mint.key, destination.key);
+    
+    let ix = spl_token_2022::instruction::transfer_checked(
+        token_program.key,
+        fee_vault_token.key,
+        mint.key,
+        destination.key,
+        &token_fee_vault.key(),
+        &[],
+        withdraw_amount,
+        mint_decimals(mint, token_program)?,
+    )?;
+    invoke_signed(
+        &ix,
+        &[
+            fee_vault_token.clone(),
+            mint.clone(),
+            destination.clone(),
+            token_fee_vault.to_account_info(),
+            token_program.clone(),
+        ],
+        &[&[b"fee_vault", token_fee_vault.mint.as_ref(), &[token_fee_vault.bump]]],
+    )?;
+    
+    Ok(withdraw_amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn match_with(stake_amount: u64, maker_fee_bps: u16, taker_fee_bps: u16, min_fee: u64, max_fee: u64) -> Match {
+        let mut match_account = Match::try_deserialize_unchecked(&mut &[0u8; Match::LEN][..]).unwrap();
+        match_account.stake_amount = stake_amount;
+        match_account.maker_fee_bps = maker_fee_bps;
+        match_account.taker_fee_bps = taker_fee_bps;
+        match_account.min_fee = min_fee;
+        match_account.max_fee = max_fee;
+        match_account
+    }
+    
+    #[test]
+    fn each_stake_pays_its_own_side_fee() {
+        let match_account = match_with(1_000_000, 100, 300, 0, 0);
+        assert_eq!(stake_fees(&match_account).unwrap(), [10_000, 30_000]);
+        assert_eq!(winner_payout(&match_account).unwrap(), (40_000, 1_960_000));
+    }
+    
+    #[test]
+    fn fees_round_down() {
+        let match_account = match_with(999, 100, 100, 0, 0);
+        assert_eq!(stake_fees(&match_account).unwrap(), [9, 9]);
+        assert_eq!(winner_payout(&match_account).unwrap(), (18, 1_980));
+    }
+    
+    #[test]
+    fn min_fee_is_spread_in_proportion_to_each_side() {
+        let match_account = match_with(1_000_000, 100, 300, 80_000, 0);
+        assert_eq!(stake_fees(&match_account).unwrap(), [20_000, 60_000]);
+        assert_eq!(winner_payout(&match_account).unwrap(), (80_000, 1_920_000));
+    }
+    
+    #[test]
+    fn max_fee_caps_the_total() {
+        let match_account = match_with(1_000_000, 100, 300, 0, 20_000);
+        assert_eq!(stake_fees(&match_account).unwrap(), [5_000, 15_000]);
+        assert_eq!(winner_payout(&match_account).unwrap(), (20_000, 1_980_000));
+    }
+    
+    #[test]
+    fn min_fee_without_rates_is_split_evenly() {
+        let match_account = match_with(1_000_000, 0, 0, 1_001, 0);
+        assert_eq!(stake_fees(&match_account).unwrap(), [501, 500]);
+    }
+    
+    #[test]
+    fn fee_never_exceeds_a_stake_or_the_pot() {
+        // The host's share of the minimum is more than its stake holds
+        let match_account = match_with(100, 10_000, 0, 150, 0);
+        assert_eq!(stake_fees(&match_account).unwrap(), [100, 50]);
+        
+        // A minimum above the pot takes the whole pot
+        let match_account = match_with(100, 0, 0, 500, 0);
+        assert_eq!(stake_fees(&match_account).unwrap(), [100, 100]);
+        assert_eq!(winner_payout(&match_account).unwrap(), (200, 0));
+    }
+    
+    #[test]
+    fn winner_payout_rejects_overflowing_pot() {
+        let match_account = match_with(u64::MAX, 100, 100, 0, 0);
+        assert!(winner_payout(&match_account).is_err());
+    }
+    
+    #[test]
+    fn draw_refund_charges_draw_fee_per_stake() {
+        assert_eq!(draw_refund(1_000_000, 250).unwrap(), (25_000, 975_000));
+        assert_eq!(draw_refund(999, 100).unwrap(), (9, 990));
+        assert_eq!(draw_refund(1_000_000, 0).unwrap(), (0, 1_000_000));
+        assert_eq!(draw_refund(1_000_000, 10_000).unwrap(), (1_000_000, 0));
+    }
+}
@@ -1,30 +1,167 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::chess::{Color, Position};
+use crate::errors::EscrowError;
 
 #[account]
 pub struct Match {
     pub player_a: Pubkey,           // 32
     pub player_b: Option<Pubkey>,   // 33 (1 + 32)
-    pub stake_tier: u8,             // 1
+    pub stake_tier: u8,             // 1 (UI preset, selects the abandon timeout)
     pub join_deadline: i64,         // 8
     pub status: MatchStatus,        // 1
     pub winner: Option<Pubkey>,     // 33 (1 + 32)
     pub bump: u8,                   // 1
     pub escrow_bump: u8,            // 1
+    pub proposed_by: Option<Pubkey>, // 33 (1 + 32)
+    pub proposed_at: i64,           // 8
+    pub challenge_window: i64,      // 8
+    pub nonce: u64,                 // 8 (creation slot << 32 | game index)
+    pub disputed_by: Option<Pubkey>, // 33 (1 + 32)
+    pub dispute_bond: u64,          // 8
+    pub is_draw: bool,              // 1
+    pub draw_fee_bps: u16,          // 2
+    pub correspondence: bool,       // 1
+    pub started_at: i64,            // 8 (0 until player B joins)
+    pub abandon_timeout: i64,       // 8
+    pub invitee: Option<Pubkey>,    // 33 (1 + 32)
+    pub finished_at: i64,           // 8 (when the result became payable)
+    pub game_index: u32,            // 4 (incremented by each rematch)
+    pub player_a_white: bool,       // 1 (swapped by each rematch)
+    pub end_reason: Option<EndReason>, // 2 (1 + 1, None when the result was only proposed)
+    pub stake_amount: u64,          // 8 (each player's stake, in lamports or tokens of `mint`)
+    pub mint: Pubkey,               // 32 (default pubkey = native SOL)
+    pub token_escrow_bump: u8,      // 1
+    pub maker_fee_bps: u16,         // 2 (charged on the host's stake, snapshotted from the fee schedule)
+    pub taker_fee_bps: u16,         // 2 (charged on the joiner's stake)
+    pub min_fee: u64,               // 8
+    pub max_fee: u64,               // 8 (0 = no maximum)
+    pub dispute_deadline: i64,      // 8 (0 until disputed; after it anyone can refund the match)
 }
 
 impl Match {
-    pub const LEN: usize = 8 + 32 + 33 + 1 + 8 + 1 + 33 + 1 + 1; // 118 bytes + discriminator
+    pub const LEN: usize = 8 + 32 + 33 + 1 + 8 + 1 + 33 + 1 + 1 + 33 + 8 + 8 + 8 + 33 + 8 + 1 + 2 + 1 + 8 + 8 + 33 + 8 + 4 + 1 + 2 + 8 + 32 + 1 + 2 + 2 + 8 + 8 + 8; // 353 bytes + discriminator
+    
+    /// Size of a signed result message: match (32) + winner (32) + end reason (1) + nonce (8).
+    pub const RESULT_MESSAGE_LEN: usize = 32 + 32 + 1 + 8;
+    
+    /// Size of a referee abort attestation: "abort" (5) + match (32) + nonce (8) + deadline (8).
+    pub const ABORT_MESSAGE_LEN: usize = 5 + 32 + 8 + 8;
+    
+    /// How long after player B joins a referee abort attestation stays usable.
+    pub const REFEREE_ABORT_WINDOW: i64 = 10 * 60; // 10 minutes
+    
+    /// Size of a rematch consent: "rematch" (7) + match (32) + nonce (8).
+    pub const REMATCH_MESSAGE_LEN: usize = 7 + 32 + 8;
+    
+    /// Nonce of the first game of a match created in `slot`. The low 32 bits count
+    /// games, so nonces of a PDA recreated in a later slot never overlap earlier ones.
+    pub fn first_nonce(slot: u64) -> u64 {
+        slot << 32
+    }
+    
+    /// Nonce of the current game: the creation nonce plus the game index, so every
+    /// rematch gets a fresh receipt PDA and fresh signed messages.
+    pub fn game_nonce(&self) -> u64 {
+        (self.nonce & !(u32::MAX as u64)) | self.game_index as u64
+    }
     
     pub fn stake_amount_lamports(&self) -> u64 {
-        match self.stake_tier {
-            0 => 500_000_000,      // 0.5 SOL
-            1 => 1_000_000_000,    // 1 SOL
-            2 => 5_000_000_000,    // 5 SOL
-            3 => 10_000_000_000,   // 10 SOL
-            4 => 50_000_000,       // 0.05 SOL (test tier)
-            _ => 0,
+        self.stake_amount
+    }
+    
+    /// Snapshot the fee schedule for this match's stake tier, so later schedule
+    /// changes never affect it. Lamport fee bounds do not apply to token stakes.
+    pub fn snapshot_fees(&mut self, fee_schedule: &FeeSchedule) {
+        let tier = self.stake_tier as usize;
+        self.maker_fee_bps = fee_schedule.maker_bps.get(tier).copied().unwrap_or(Config::DEFAULT_FEE_BPS);
+        self.taker_fee_bps = fee_schedule.taker_bps.get(tier).copied().unwrap_or(Config::DEFAULT_FEE_BPS);
+        if self.is_token_match() {
+            self.min_fee = 0;
+            self.max_fee = 0;
+        } else {
+            self.min_fee = fee_schedule.min_fee;
+            self.max_fee = fee_schedule.max_fee;
         }
     }
+
+    /// Returns the other player in the match, if `player` is one of them.
+    pub fn opponent_of(&self, player: &Pubkey) -> Option<Pubkey> {
+        let player_b = self.player_b?;
+        if *player == self.player_a {
+            Some(player_b)
+        } else if *player == player_b {
+            Some(self.player_a)
+        } else {
+            None
+        }
+    }
+
+    /// Bytes an off-chain party signs to attest the result of this match.
+    pub fn result_message(
+        &self,
+        match_key: &Pubkey,
+        winner: &Pubkey,
+        end_reason: EndReason,
+    ) -> [u8; Self::RESULT_MESSAGE_LEN] {
+        let mut message = [0u8; Self::RESULT_MESSAGE_LEN];
+        message[..32].copy_from_slice(match_key.as_ref());
+        message[32..64].copy_from_slice(winner.as_ref());
+        message[64] = end_reason as u8;
+        message[65..].copy_from_slice(&self.nonce.to_le_bytes());
+        message
+    }
+    
+    /// Earliest time either player may abandon the match.
+    pub fn abandon_allowed_at(&self) -> i64 {
+        self.started_at.saturating_add(self.abandon_timeout)
+    }
+    
+    /// Last time a referee abort attestation can be used, so one signed before the
+    /// first move cannot be replayed once the game is under way.
+    pub fn referee_abort_deadline(&self) -> i64 {
+        self.started_at.saturating_add(Self::REFEREE_ABORT_WINDOW)
+    }
+    
+    /// Bytes the referee signs to attest that no move has been played in this match.
+    pub fn abort_message(&self, match_key: &Pubkey) -> [u8; Self::ABORT_MESSAGE_LEN] {
+        let mut message = [0u8; Self::ABORT_MESSAGE_LEN];
+        message[..5].copy_from_slice(b"abort");
+        message[5..37].copy_from_slice(match_key.as_ref());
+        message[37..45].copy_from_slice(&self.nonce.to_le_bytes());
+        message[45..].copy_from_slice(&self.referee_abort_deadline().to_le_bytes());
+        message
+    }
+    
+    /// Whether the stakes are held in an SPL mint rather than native SOL.
+    pub fn is_token_match(&self) -> bool {
+        self.mint != Pubkey::default()
+    }
+    
+    /// Stake the escrow owes out (lamports, or tokens of `mint`): each deposited stake.
+    /// Dispute bonds are always held in lamports on top of this.
+    pub fn stake_liabilities(&self) -> Result<u64> {
+        let stakes = if self.player_b.is_some() { 2 } else { 1 };
+        let liabilities = self
+            .stake_amount_lamports()
+            .checked_mul(stakes)
+            .ok_or(EscrowError::ArithmeticOverflow)?;
+        Ok(liabilities)
+    }
+    
+    /// Bytes a player signs to agree to a rematch instead of signing the transaction.
+    pub fn rematch_message(&self, match_key: &Pubkey) -> [u8; Self::REMATCH_MESSAGE_LEN] {
+        let mut message = [0u8; Self::REMATCH_MESSAGE_LEN];
+        message[..7].copy_from_slice(b"rematch");
+        message[7..39].copy_from_slice(match_key.as_ref());
+        message[39..].copy_from_slice(&self.nonce.to_le_bytes());
+        message
+    }
+    
+    /// Whether a proposed result can still be contested at `now`.
+    pub fn challenge_window_open(&self, now: i64) -> bool {
+        now < self.proposed_at.saturating_add(self.challenge_window)
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -33,6 +170,107 @@ pub enum MatchStatus {
     Active,     // Both players joined, game in progress
     Finished,   // Winner declared, ready for payout
     Cancelled,  // Cancelled by creator before join
+    ResultProposed, // Winner or draw proposed by one player, awaiting confirmation or challenge
+    Contested,  // Proposed result contested by the opponent
+    Disputed,   // Bond posted, awaiting arbiter resolution
+    Drawn,      // Draw agreed, ready for stakes to be returned
+    Aborted,    // Called off by both players before play started, stakes refunded
+    Abandoned,  // Abandoned after the timeout without a result, stakes refunded
+}
+
+/// Outcome chosen by the arbiter for a disputed match.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DisputeResolution {
+    PlayerA,    // Player A wins, pot paid out as with confirm_payout
+    PlayerB,    // Player B wins, pot paid out as with confirm_payout
+    Draw,       // Game drawn, each stake returned minus the draw fee
+    Split,      // Result undeterminable, each stake returned minus its own side's platform fee
+}
+
+/// How a game ended, mirroring the backend's `endReason`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EndReason {
+    Checkmate,
+    Timeout,
+    Resignation,
+    Draw,
+}
+
+/// Final outcome of a game, as recorded in its receipt.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MatchOutcome {
+    Won,        // Winner paid out
+    Drawn,      // Stakes returned minus the draw fee
+    Split,      // Arbiter split the pot
+    Cancelled,  // Cancelled before anyone joined
+    Aborted,    // Called off before play started
+    Abandoned,  // Abandoned without a result
+    Refunded,   // Force refunded after an unclaimed payout
+    Unresolved, // Dispute deadline passed without a ruling, stakes and bond refunded
+    Expired,    // Join deadline passed without an opponent, stake refunded
+}
+
+/// Permanent record of a settled game, kept after the match account is closed.
+/// Seeds include the match nonce, so every game (including rematches) gets its own receipt.
+#[account]
+pub struct MatchReceipt {
+    pub match_account: Pubkey,        // 32
+    pub owner: Pubkey,                // 32 (paid the rent, can prune it)
+    pub player_a: Pubkey,             // 32
+    pub player_b: Option<Pubkey>,     // 33 (1 + 32)
+    pub stake_tier: u8,               // 1
+    pub stake_amount: u64,            // 8
+    pub game_index: u32,              // 4
+    pub outcome: MatchOutcome,        // 1
+    pub winner: Option<Pubkey>,       // 33 (1 + 32)
+    pub end_reason: Option<EndReason>, // 2 (1 + 1)
+    pub fee: u64,                     // 8
+    pub paid_to_player_a: u64,        // 8
+    pub paid_to_player_b: u64,        // 8
+    pub started_at: i64,              // 8
+    pub finished_at: i64,             // 8
+    pub settled_at: i64,              // 8
+    pub mint: Pubkey,                 // 32 (default pubkey = native SOL)
+}
+
+impl MatchReceipt {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 33 + 1 + 8 + 4 + 1 + 33 + 2 + 8 + 8 + 8 + 8 + 8 + 8 + 32; // 266 bytes + discriminator
+    
+    /// Fill in the receipt for the game currently held by `match_account`.
+    /// `paid` is what player A and player B received, `fee` what went to the fee vault.
+    pub fn record(
+        &mut self,
+        match_key: Pubkey,
+        match_account: &Match,
+        outcome: MatchOutcome,
+        paid: [u64; 2],
+        fee: u64,
+        owner: Pubkey,
+    ) -> Result<()> {
+        self.match_account = match_key;
+        self.owner = owner;
+        self.player_a = match_account.player_a;
+        self.player_b = match_account.player_b;
+        self.stake_tier = match_account.stake_tier;
+        self.stake_amount = match_account.stake_amount_lamports();
+        self.game_index = match_account.game_index;
+        self.outcome = outcome;
+        self.winner = if outcome == MatchOutcome::Won { match_account.winner } else { None };
+        self.end_reason = match_account.end_reason;
+        self.fee = fee;
+        self.paid_to_player_a = paid[0];
+        self.paid_to_player_b = paid[1];
+        self.started_at = match_account.started_at;
+        self.settled_at = Clock::get()?.unix_timestamp;
+        // Matches that end without a result (cancels, expiries, aborts) finish when settled
+        self.finished_at = if match_account.finished_at != 0 {
+            match_account.finished_at
+        } else {
+            self.settled_at
+        };
+        self.mint = match_account.mint;
+        Ok(())
+    }
 }
 
 #[account]
@@ -44,3 +282,684 @@ pub struct FeeVault {
 impl FeeVault {
     pub const LEN: usize = 8 + 8 + 1; // 17 bytes + discriminator
 }
+
+/// Fee vault of one SPL mint, created by the admin to allow stakes in that mint.
+/// Fees are held in the token account at `["fee_vault_token", mint]`, owned by this PDA.
+#[account]
+pub struct TokenFeeVault {
+    pub mint: Pubkey,           // 32
+    pub total_collected: u64,   // 8
+    pub min_stake: u64,         // 8
+    pub max_stake: u64,         // 8
+    pub bump: u8,               // 1
+    pub token_account_bump: u8, // 1
+}
+
+impl TokenFeeVault {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 1 + 1; // 66 bytes + discriminator
+    
+    /// Whether `stake_amount` lies within this mint's stake bounds.
+    pub fn stake_valid(&self, stake_amount: u64) -> bool {
+        (self.min_stake..=self.max_stake).contains(&stake_amount)
+    }
+}
+
+#[account]
+pub struct Config {
+    pub challenge_window: i64,      // 8
+    pub referee: Pubkey,            // 32 (default pubkey = attestations disabled)
+    pub arbiter: Pubkey,            // 32 (default pubkey = disputes disabled)
+    pub dispute_bond: u64,          // 8
+    pub draw_fee_bps: u16,          // 2 (charged on each stake)
+    pub abandon_timeouts: [i64; Config::MAX_STAKE_TIERS], // 64 (seconds after start, indexed by stake tier)
+    pub expiry_bounty: u64,         // 8 (paid out of the expired match's rent)
+    pub min_join_window: i64,       // 8
+    pub max_join_window: i64,       // 8
+    pub force_refund_grace: i64,    // 8 (seconds after finishing before force_refund is allowed)
+    pub min_stake: u64,             // 8
+    pub max_stake: u64,             // 8
+    pub bump: u8,                   // 1
+    pub stake_tiers: [StakeTier; Config::MAX_STAKE_TIERS], // 72 (indexed by stake tier)
+    pub fee_schedule: FeeSchedule,  // 48
+    pub admin: Pubkey,              // 32 (parameter admin; also nominates holders of the other roles)
+    pub fee_withdrawer: Pubkey,     // 32
+    pub pauser: Pubkey,             // 32
+    pub pending_roles: [Pubkey; Role::COUNT], // 160 (nominee per role, default pubkey = none)
+    pub fee_multisig: FeeMultisig,  // 189
+    pub paused: u8,                 // 1 (bitmask of Config::PAUSE_* flags)
+    pub dispute_timeout: i64,       // 8 (seconds the arbiter has to resolve a dispute)
+}
+
+/// M-of-N signers that approve fee withdrawals through `WithdrawalProposal`s.
+/// While enabled (threshold above 0), direct fee withdrawals are rejected.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct FeeMultisig {
+    pub signers: [Pubkey; FeeMultisig::MAX_SIGNERS], // 160 (default pubkey = empty slot)
+    pub threshold: u8,              // 1 (0 = multisig disabled)
+    pub delay: i64,                 // 8 (seconds between reaching the threshold and execution)
+    pub proposal_ttl: i64,          // 8 (seconds a proposal stays valid after creation)
+    pub version: u32,               // 4 (bumped on every change, invalidating open proposals)
+    pub proposal_count: u64,        // 8 (seeds the next proposal)
+}
+
+impl FeeMultisig {
+    pub const MAX_SIGNERS: usize = 5;
+    pub const MAX_DELAY: i64 = 30 * 24 * 60 * 60;        // 30 days
+    pub const MIN_PROPOSAL_TTL: i64 = 60 * 60;           // 1 hour
+    pub const MAX_PROPOSAL_TTL: i64 = 90 * 24 * 60 * 60; // 90 days
+    
+    pub fn is_enabled(&self) -> bool {
+        self.threshold > 0
+    }
+    
+    /// Slot of `signer` in the signer set.
+    pub fn signer_index(&self, signer: &Pubkey) -> Option<usize> {
+        if *signer == Pubkey::default() {
+            return None;
+        }
+        self.signers.iter().position(|key| key == signer)
+    }
+    
+    /// Whether the threshold is reachable by distinct signers and the timings are in range.
+    pub fn is_valid(&self) -> bool {
+        let mut signer_count = 0;
+        for (index, signer) in self.signers.iter().enumerate() {
+            if *signer == Pubkey::default() {
+                continue;
+            }
+            if self.signers[..index].contains(signer) {
+                return false;
+            }
+            signer_count += 1;
+        }
+        
+        (self.threshold as usize) <= signer_count
+            && (0..=Self::MAX_DELAY).contains(&self.delay)
+            && (Self::MIN_PROPOSAL_TTL..=Self::MAX_PROPOSAL_TTL).contains(&self.proposal_ttl)
+    }
+}
+
+/// A fee withdrawal awaiting approval by the fee multisig.
+#[account]
+pub struct WithdrawalProposal {
+    pub id: u64,                    // 8
+    pub proposer: Pubkey,           // 32 (paid the rent, gets it back on close)
+    pub mint: Pubkey,               // 32 (default pubkey = SOL fee vault)
+    pub amount: u64,                // 8
+    pub recipient: Pubkey,          // 32 (receives SOL, or owns the destination token account)
+    pub approvals: u8,              // 1 (bitmask over signer slots)
+    pub multisig_version: u32,      // 4
+    pub created_at: i64,            // 8
+    pub approved_at: i64,           // 8 (0 until the threshold is reached)
+    pub expires_at: i64,            // 8
+    pub bump: u8,                   // 1
+}
+
+impl WithdrawalProposal {
+    pub const LEN: usize = 8 + 8 + 32 + 32 + 8 + 32 + 1 + 4 + 8 + 8 + 8 + 1; // 150 bytes + discriminator
+    
+    pub fn approval_count(&self) -> u32 {
+        self.approvals.count_ones()
+    }
+    
+    /// Record an approval from signer slot `index`, noting when the threshold is reached.
+    pub fn approve(&mut self, index: usize, threshold: u8, now: i64) -> Result<()> {
+        let bit = 1u8 << index;
+        require!(self.approvals & bit == 0, EscrowError::AlreadyApproved);
+        self.approvals |= bit;
+        if self.approved_at == 0 && self.approval_count() >= threshold as u32 {
+            self.approved_at = now;
+        }
+        Ok(())
+    }
+}
+
+/// Authorities stored in `Config`. Each is rotated with `propose_role` / `accept_role`
+/// by its own holder, or by the admin for any role but the admin role.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    Admin,
+    FeeWithdrawer,
+    Pauser,
+    Arbiter,
+    Referee,
+}
+
+impl Role {
+    pub const COUNT: usize = 5;
+    
+    /// Error returned when a signer does not hold this role.
+    pub fn violation(self) -> EscrowError {
+        match self {
+            Role::Admin => EscrowError::NotAdmin,
+            Role::FeeWithdrawer => EscrowError::NotFeeWithdrawer,
+            Role::Pauser => EscrowError::NotPauser,
+            Role::Arbiter => EscrowError::OnlyArbiter,
+            Role::Referee => EscrowError::NotReferee,
+        }
+    }
+    
+    /// Whether one key may hold both roles. Whoever withdraws fees must not
+    /// also decide disputes or attest results.
+    pub fn compatible_with(self, other: Role) -> bool {
+        !matches!(
+            (self, other),
+            (Role::FeeWithdrawer, Role::Arbiter | Role::Referee) | (Role::Arbiter | Role::Referee, Role::FeeWithdrawer)
+        )
+    }
+}
+
+/// Platform fee charged on the pot of a decided match, snapshotted into each match.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct FeeSchedule {
+    pub maker_bps: [u16; Config::MAX_STAKE_TIERS], // 16 (charged on the host's stake, indexed by stake tier)
+    pub taker_bps: [u16; Config::MAX_STAKE_TIERS], // 16 (charged on the joiner's stake)
+    pub min_fee: u64,               // 8 (lamports, SOL matches only)
+    pub max_fee: u64,               // 8 (lamports, SOL matches only, 0 = no maximum)
+}
+
+impl FeeSchedule {
+    /// Whether all rates are valid basis points and the bounds are ordered.
+    pub fn is_valid(&self) -> bool {
+        self.maker_bps.iter().chain(self.taker_bps.iter()).all(|bps| *bps <= Config::MAX_BPS)
+            && (self.max_fee == 0 || self.min_fee <= self.max_fee)
+    }
+}
+
+/// Entry of the stake tier table: a preset stake the admin can re-price or disable.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct StakeTier {
+    pub lamports: u64,              // 8
+    pub enabled: bool,              // 1
+}
+
+impl Config {
+    pub const LEN: usize = 8 + 8 + 32 + 32 + 8 + 2 + 64 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 72 + 48 + 32 + 32 + 32 + 160 + 189 + 1 + 8; // 777 bytes + discriminator
+
+    // `devnet` builds allow much shorter timeouts so flows can be tested by hand
+    #[cfg(not(feature = "devnet"))]
+    pub const DEFAULT_CHALLENGE_WINDOW: i64 = 15 * 60;  // 15 minutes
+    #[cfg(not(feature = "devnet"))]
+    pub const MIN_CHALLENGE_WINDOW: i64 = 60;           // 1 minute
+    #[cfg(feature = "devnet")]
+    pub const DEFAULT_CHALLENGE_WINDOW: i64 = 60;       // 1 minute
+    #[cfg(feature = "devnet")]
+    pub const MIN_CHALLENGE_WINDOW: i64 = 10;           // 10 seconds
+    pub const MAX_CHALLENGE_WINDOW: i64 = 24 * 60 * 60; // 24 hours
+    
+    pub const DEFAULT_DISPUTE_BOND: u64 = 100_000_000;  // 0.1 SOL
+    
+    #[cfg(not(feature = "devnet"))]
+    pub const DEFAULT_DISPUTE_TIMEOUT: i64 = 7 * 24 * 60 * 60; // 7 days
+    #[cfg(not(feature = "devnet"))]
+    pub const MIN_DISPUTE_TIMEOUT: i64 = 24 * 60 * 60;         // 24 hours
+    #[cfg(feature = "devnet")]
+    pub const DEFAULT_DISPUTE_TIMEOUT: i64 = 30 * 60;          // 30 minutes
+    #[cfg(feature = "devnet")]
+    pub const MIN_DISPUTE_TIMEOUT: i64 = 60;                   // 1 minute
+    pub const MAX_DISPUTE_TIMEOUT: i64 = 30 * 24 * 60 * 60;    // 30 days
+    
+    pub const DEFAULT_DRAW_FEE_BPS: u16 = 0;
+    pub const DEFAULT_FEE_BPS: u16 = 1_000;             // 10% of each stake, i.e. 10% of the pot
+    pub const MAX_BPS: u16 = 10_000;
+    
+    #[cfg(not(feature = "devnet"))]
+    pub const DEFAULT_ABANDON_TIMEOUT: i64 = 60 * 60;       // 1 hour
+    #[cfg(not(feature = "devnet"))]
+    pub const MIN_ABANDON_TIMEOUT: i64 = 5 * 60;            // 5 minutes
+    #[cfg(feature = "devnet")]
+    pub const DEFAULT_ABANDON_TIMEOUT: i64 = 5 * 60;        // 5 minutes
+    #[cfg(feature = "devnet")]
+    pub const MIN_ABANDON_TIMEOUT: i64 = 30;                // 30 seconds
+    pub const MAX_ABANDON_TIMEOUT: i64 = 7 * 24 * 60 * 60;  // 7 days
+    
+    pub const MAX_EXPIRY_BOUNTY: u64 = 1_000_000;       // 0.001 SOL, well under a match account's rent
+    
+    pub const DEFAULT_MIN_JOIN_WINDOW: i64 = 60;                // 1 minute
+    pub const DEFAULT_MAX_JOIN_WINDOW: i64 = 7 * 24 * 60 * 60;  // 7 days
+    
+    #[cfg(not(feature = "devnet"))]
+    pub const DEFAULT_FORCE_REFUND_GRACE: i64 = 24 * 60 * 60;  // 24 hours
+    #[cfg(not(feature = "devnet"))]
+    pub const MIN_FORCE_REFUND_GRACE: i64 = 60 * 60;           // 1 hour
+    #[cfg(feature = "devnet")]
+    pub const DEFAULT_FORCE_REFUND_GRACE: i64 = 10 * 60;       // 10 minutes
+    #[cfg(feature = "devnet")]
+    pub const MIN_FORCE_REFUND_GRACE: i64 = 60;                // 1 minute
+    pub const MAX_FORCE_REFUND_GRACE: i64 = 30 * 24 * 60 * 60; // 30 days
+    
+    pub const DEFAULT_MIN_STAKE: u64 = 50_000_000;      // 0.05 SOL
+    pub const DEFAULT_MAX_STAKE: u64 = 10_000_000_000;  // 10 SOL
+    pub const MIN_STAKE_FLOOR: u64 = 1_000_000;         // 0.001 SOL, keeps a fresh escrow rent-exempt
+    
+    pub const MAX_STAKE_TIERS: usize = 8;
+    /// Tiers 0-3 are the 0.5, 1, 5 and 10 SOL presets, tier 4 the 0.05 SOL test tier
+    pub const DEFAULT_STAKE_TIERS: [u64; 5] = [500_000_000, 1_000_000_000, 5_000_000_000, 10_000_000_000, 50_000_000];
+    pub const TEST_STAKE_TIER: u8 = 4;
+    
+    /// Whether this build accepts `stake_tier`; the test tier only exists in `devnet` builds.
+    pub fn tier_allowed(stake_tier: u8) -> bool {
+        cfg!(feature = "devnet") || stake_tier != Self::TEST_STAKE_TIER
+    }
+    
+    pub const ROLES: [Role; Role::COUNT] = [Role::Admin, Role::FeeWithdrawer, Role::Pauser, Role::Arbiter, Role::Referee];
+    
+    // Pause flags. Refunds, cancellations, dispute handling and payouts of
+    // settled games are never paused, so users can always get their funds out.
+    pub const PAUSE_CREATE: u8 = 1 << 0;  // create_match, update_open_match, enable_correspondence, rematch
+    pub const PAUSE_JOIN: u8 = 1 << 1;    // join_match
+    pub const PAUSE_RESULTS: u8 = 1 << 2; // submit_result, submit_draw, settle_with_attestation, settle_mutual
+    pub const PAUSE_ALL: u8 = Self::PAUSE_CREATE | Self::PAUSE_JOIN | Self::PAUSE_RESULTS;
+    
+    /// Whether any of `flags` is paused.
+    pub fn is_paused(&self, flags: u8) -> bool {
+        self.paused & flags != 0
+    }
+    
+    /// Current holder of `role`; the default pubkey means the role is vacant.
+    pub fn role_holder(&self, role: Role) -> Pubkey {
+        match role {
+            Role::Admin => self.admin,
+            Role::FeeWithdrawer => self.fee_withdrawer,
+            Role::Pauser => self.pauser,
+            Role::Arbiter => self.arbiter,
+            Role::Referee => self.referee,
+        }
+    }
+    
+    pub fn set_role_holder(&mut self, role: Role, holder: Pubkey) {
+        match role {
+            Role::Admin => self.admin = holder,
+            Role::FeeWithdrawer => self.fee_withdrawer = holder,
+            Role::Pauser => self.pauser = holder,
+            Role::Arbiter => self.arbiter = holder,
+            Role::Referee => self.referee = holder,
+        }
+    }
+    
+    /// Require `signer` to hold `role`, failing with that role's error.
+    pub fn require_role(&self, role: Role, signer: &Pubkey) -> Result<()> {
+        let holder = self.role_holder(role);
+        require!(holder != Pubkey::default() && holder == *signer, role.violation());
+        Ok(())
+    }
+    
+    /// Whether `key` holds a role that may not be combined with `role`.
+    pub fn role_conflicts(&self, role: Role, key: &Pubkey) -> bool {
+        Self::ROLES
+            .iter()
+            .any(|other| !role.compatible_with(*other) && self.role_holder(*other) == *key)
+    }
+    
+    /// Whether `join_deadline` is an acceptable deadline for a match created or updated at `now`.
+    pub fn join_deadline_valid(&self, join_deadline: i64, now: i64) -> bool {
+        join_deadline >= now.saturating_add(self.min_join_window)
+            && join_deadline <= now.saturating_add(self.max_join_window)
+    }
+    
+    /// Whether `stake_amount` lies within the configured stake bounds.
+    pub fn stake_valid(&self, stake_amount: u64) -> bool {
+        (self.min_stake..=self.max_stake).contains(&stake_amount)
+    }
+    
+    /// Enabled entry of the stake tier table for `stake_tier`, if this build allows the tier.
+    pub fn stake_tier(&self, stake_tier: u8) -> Option<&StakeTier> {
+        self.stake_tiers
+            .get(stake_tier as usize)
+            .filter(|tier| tier.enabled && Self::tier_allowed(stake_tier))
+    }
+    
+    /// Abandon timeout for matches of `stake_tier`.
+    pub fn abandon_timeout(&self, stake_tier: u8) -> i64 {
+        self.abandon_timeouts
+            .get(stake_tier as usize)
+            .copied()
+            .unwrap_or(Self::DEFAULT_ABANDON_TIMEOUT)
+    }
+}
+
+/// Chess position as stored in program accounts.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct BoardState {
+    pub board: [u8; 64],            // 64
+    pub side_to_move: u8,           // 1 (0 = white, 1 = black)
+    pub castling: u8,               // 1
+    pub en_passant: u8,             // 1
+}
+
+impl BoardState {
+    pub const LEN: usize = 64 + 1 + 1 + 1;
+    
+    pub fn position(&self) -> Position {
+        Position {
+            board: self.board,
+            side_to_move: if self.side_to_move == 0 { Color::White } else { Color::Black },
+            castling: self.castling,
+            en_passant: self.en_passant,
+        }
+    }
+    
+    pub fn set_position(&mut self, position: &Position) {
+        self.board = position.board;
+        self.side_to_move = match position.side_to_move {
+            Color::White => 0,
+            Color::Black => 1,
+        };
+        self.castling = position.castling;
+        self.en_passant = position.en_passant;
+    }
+}
+
+/// Board state of a transcript being replayed move by move to prove a result.
+/// Replays can span several transactions to stay within compute limits.
+/// Each replay is bound to one game of its match through the nonce and game index.
+#[account]
+pub struct GameReplay {
+    pub match_account: Pubkey,      // 32
+    pub prover: Pubkey,             // 32
+    pub board: BoardState,          // 67
+    pub ply: u16,                   // 2
+    pub transcript_hash: [u8; 32],  // 32
+    pub nonce: u64,                 // 8 (match nonce of the replayed game)
+    pub game_index: u32,            // 4
+    pub bump: u8,                   // 1
+}
+
+impl GameReplay {
+    pub const LEN: usize = 8 + 32 + 32 + BoardState::LEN + 2 + 32 + 8 + 4 + 1; // 186 bytes + discriminator
+    
+    /// Size of a signed transcript message: match (32) + nonce (8) + game index (4)
+    /// + transcript hash (32) + mover color (1) + terminal flag (1).
+    pub const TRANSCRIPT_MESSAGE_LEN: usize = 32 + 8 + 4 + 32 + 1 + 1;
+    
+    /// Transcript hash before any move, bound to a single game of a match.
+    pub fn initial_hash(match_key: &Pubkey, nonce: u64, game_index: u32) -> [u8; 32] {
+        hashv(&[b"transcript", match_key.as_ref(), &nonce.to_le_bytes(), &game_index.to_le_bytes()]).to_bytes()
+    }
+    
+    /// Reset to the starting position with an empty transcript of the current game.
+    pub fn reset(&mut self, match_key: &Pubkey, match_account: &Match) {
+        self.board.set_position(&Position::start());
+        self.ply = 0;
+        self.nonce = match_account.nonce;
+        self.game_index = match_account.game_index;
+        self.transcript_hash = Self::initial_hash(match_key, self.nonce, self.game_index);
+    }
+    
+    /// Whether this replay belongs to the game currently held by `match_account`.
+    pub fn is_current(&self, match_account: &Match) -> bool {
+        self.nonce == match_account.nonce && self.game_index == match_account.game_index
+    }
+    
+    /// Extend the transcript hash chain with `raw_move`.
+    pub fn record_move(&mut self, raw_move: u16) {
+        self.transcript_hash = hashv(&[&self.transcript_hash, &raw_move.to_le_bytes()]).to_bytes();
+        self.ply = self.ply.saturating_add(1);
+    }
+    
+    /// Bytes signed over a transcript: the game, the transcript hash and the color
+    /// that made its last move. Players sign with `terminal` unset after each of
+    /// their moves; only a transcript signed with `terminal` set is a finished game.
+    pub fn transcript_message(
+        &self,
+        match_key: &Pubkey,
+        mover: Color,
+        terminal: bool,
+    ) -> [u8; Self::TRANSCRIPT_MESSAGE_LEN] {
+        let mut message = [0u8; Self::TRANSCRIPT_MESSAGE_LEN];
+        message[..32].copy_from_slice(match_key.as_ref());
+        message[32..40].copy_from_slice(&self.nonce.to_le_bytes());
+        message[40..44].copy_from_slice(&self.game_index.to_le_bytes());
+        message[44..76].copy_from_slice(&self.transcript_hash);
+        message[76] = match mover {
+            Color::White => 0,
+            Color::Black => 1,
+        };
+        message[77] = terminal as u8;
+        message
+    }
+}
+
+/// Live game state of a correspondence match, where every move is played on-chain.
+#[account]
+pub struct CorrespondenceGame {
+    pub match_account: Pubkey,      // 32
+    pub host: Pubkey,               // 32
+    pub host_plays_white: bool,     // 1
+    pub board: BoardState,          // 67
+    pub ply: u16,                   // 2
+    pub seconds_per_move: i64,      // 8
+    pub turn_started_at: i64,       // 8 (0 until player B joins)
+    pub bump: u8,                   // 1
+    pub nonce: u64,                 // 8 (nonce of the match the game belongs to)
+}
+
+impl CorrespondenceGame {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + BoardState::LEN + 2 + 8 + 8 + 1 + 8; // 167 bytes + discriminator
+    
+    #[cfg(not(feature = "devnet"))]
+    pub const MIN_SECONDS_PER_MOVE: i64 = 60 * 60;            // 1 hour
+    #[cfg(feature = "devnet")]
+    pub const MIN_SECONDS_PER_MOVE: i64 = 60;                 // 1 minute
+    pub const MAX_SECONDS_PER_MOVE: i64 = 14 * 24 * 60 * 60;  // 14 days
+    
+    /// The player holding `color` in `match_account`.
+    pub fn player(&self, match_account: &Match, color: Color) -> Option<Pubkey> {
+        let host_color = if self.host_plays_white { Color::White } else { Color::Black };
+        if color == host_color {
+            Some(match_account.player_a)
+        } else {
+            match_account.player_b
+        }
+    }
+    
+    /// Whether the game was set up for the match's current nonce, rather than left
+    /// behind by an earlier match at the same address.
+    pub fn belongs_to(&self, match_account: &Match) -> bool {
+        self.nonce == match_account.nonce
+    }
+    
+    /// Deadline for the side to move. Only meaningful once the clock has started.
+    pub fn move_deadline(&self) -> i64 {
+        self.turn_started_at.saturating_add(self.seconds_per_move)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn multisig(signers: &[Pubkey], threshold: u8) -> FeeMultisig {
+        let mut multisig = FeeMultisig {
+            threshold,
+            proposal_ttl: FeeMultisig::MIN_PROPOSAL_TTL,
+            ..FeeMultisig::default()
+        };
+        multisig.signers[..signers.len()].copy_from_slice(signers);
+        multisig
+    }
+    
+    fn proposal() -> WithdrawalProposal {
+        WithdrawalProposal {
+            id: 0,
+            proposer: Pubkey::new_unique(),
+            mint: Pubkey::default(),
+            amount: 1_000,
+            recipient: Pubkey::new_unique(),
+            approvals: 0,
+            multisig_version: 0,
+            created_at: 0,
+            approved_at: 0,
+            expires_at: 0,
+            bump: 0,
+        }
+    }
+    
+    #[test]
+    fn multisig_with_threshold_zero_is_valid_but_disabled() {
+        let multisig = multisig(&[], 0);
+        assert!(multisig.is_valid());
+        assert!(!multisig.is_enabled());
+    }
+    
+    #[test]
+    fn multisig_threshold_must_be_reachable() {
+        let signers = [Pubkey::new_unique(), Pubkey::new_unique()];
+        assert!(multisig(&signers, 2).is_valid());
+        assert!(!multisig(&signers, 3).is_valid());
+    }
+    
+    #[test]
+    fn multisig_rejects_duplicate_signers() {
+        let signer = Pubkey::new_unique();
+        assert!(!multisig(&[signer, Pubkey::new_unique(), signer], 2).is_valid());
+    }
+    
+    #[test]
+    fn multisig_ignores_empty_slots() {
+        let signers = [Pubkey::new_unique(), Pubkey::default(), Pubkey::new_unique()];
+        let multisig = multisig(&signers, 2);
+        assert!(multisig.is_valid());
+        assert_eq!(multisig.signer_index(&signers[2]), Some(2));
+        assert_eq!(multisig.signer_index(&Pubkey::default()), None);
+    }
+    
+    #[test]
+    fn multisig_timings_must_be_in_range() {
+        let signers = [Pubkey::new_unique()];
+        let mut multisig = multisig(&signers, 1);
+        multisig.delay = FeeMultisig::MAX_DELAY + 1;
+        assert!(!multisig.is_valid());
+        multisig.delay = 0;
+        multisig.proposal_ttl = FeeMultisig::MIN_PROPOSAL_TTL - 1;
+        assert!(!multisig.is_valid());
+    }
+    
+    #[test]
+    fn proposal_is_approved_once_threshold_is_reached() {
+        let mut proposal = proposal();
+        proposal.approve(0, 2, 10).unwrap();
+        assert_eq!(proposal.approval_count(), 1);
+        assert_eq!(proposal.approved_at, 0);
+        
+        proposal.approve(3, 2, 20).unwrap();
+        assert_eq!(proposal.approval_count(), 2);
+        assert_eq!(proposal.approved_at, 20);
+        
+        // Later approvals keep the time the threshold was first reached
+        proposal.approve(1, 2, 30).unwrap();
+        assert_eq!(proposal.approval_count(), 3);
+        assert_eq!(proposal.approved_at, 20);
+    }
+    
+    #[test]
+    fn proposal_rejects_repeated_approval() {
+        let mut proposal = proposal();
+        proposal.approve(1, 2, 10).unwrap();
+        assert!(proposal.approve(1, 2, 20).is_err());
+        assert_eq!(proposal.approval_count(), 1);
+        assert_eq!(proposal.approved_at, 0);
+    }
+    
+    fn config() -> Config {
+        Config::try_deserialize_unchecked(&mut &[0u8; Config::LEN][..]).unwrap()
+    }
+    
+    #[test]
+    fn stake_must_lie_within_config_bounds() {
+        let mut config = config();
+        config.min_stake = Config::DEFAULT_MIN_STAKE;
+        config.max_stake = Config::DEFAULT_MAX_STAKE;
+        assert!(config.stake_valid(Config::DEFAULT_MIN_STAKE));
+        assert!(config.stake_valid(123_456_789));
+        assert!(config.stake_valid(Config::DEFAULT_MAX_STAKE));
+        assert!(!config.stake_valid(Config::DEFAULT_MIN_STAKE - 1));
+        assert!(!config.stake_valid(Config::DEFAULT_MAX_STAKE + 1));
+    }
+    
+    #[test]
+    fn token_stake_must_lie_within_mint_bounds() {
+        let vault = TokenFeeVault {
+            mint: Pubkey::new_unique(),
+            total_collected: 0,
+            min_stake: 1_000,
+            max_stake: 5_000,
+            bump: 0,
+            token_account_bump: 0,
+        };
+        assert!(vault.stake_valid(1_000));
+        assert!(vault.stake_valid(5_000));
+        assert!(!vault.stake_valid(999));
+        assert!(!vault.stake_valid(5_001));
+    }
+    
+    #[test]
+    fn join_deadline_must_lie_within_join_window() {
+        let mut config = config();
+        config.min_join_window = 60;
+        config.max_join_window = 3_600;
+        let now = 1_700_000_000;
+        assert!(config.join_deadline_valid(now + 60, now));
+        assert!(config.join_deadline_valid(now + 3_600, now));
+        assert!(!config.join_deadline_valid(now + 59, now));
+        assert!(!config.join_deadline_valid(now + 3_601, now));
+        assert!(!config.join_deadline_valid(now - 1, now));
+    }
+    
+    #[test]
+    fn join_deadline_check_does_not_overflow() {
+        let mut config = config();
+        config.min_join_window = 60;
+        config.max_join_window = i64::MAX;
+        assert!(config.join_deadline_valid(i64::MAX, i64::MAX - 60));
+        assert!(!config.join_deadline_valid(i64::MAX - 1, i64::MAX - 60));
+    }
+    
+    fn new_match() -> Match {
+        Match::try_deserialize_unchecked(&mut &[0u8; Match::LEN][..]).unwrap()
+    }
+    
+    #[test]
+    fn first_nonce_leaves_low_bits_for_game_index() {
+        let mut match_account = new_match();
+        match_account.nonce = Match::first_nonce(5);
+        assert_eq!(match_account.nonce, 5 << 32);
+        assert_eq!(match_account.game_nonce(), match_account.nonce);
+    }
+    
+    #[test]
+    fn game_nonce_follows_game_index() {
+        let mut match_account = new_match();
+        match_account.nonce = Match::first_nonce(7);
+        for game_index in 1..=3 {
+            match_account.game_index = game_index;
+            match_account.nonce = match_account.game_nonce();
+            assert_eq!(match_account.nonce, (7 << 32) | game_index as u64);
+        }
+    }
+    
+    #[test]
+    fn nonces_of_a_recreated_match_never_overlap() {
+        let mut match_account = new_match();
+        match_account.nonce = Match::first_nonce(7);
+        match_account.game_index = u32::MAX;
+        assert!(match_account.game_nonce() < Match::first_nonce(8));
+    }
+    
+    #[test]
+    fn result_message_binds_match_winner_reason_and_nonce() {
+        let (match_key, winner) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut match_account = new_match();
+        match_account.nonce = Match::first_nonce(9);
+        let message = match_account.result_message(&match_key, &winner, EndReason::Resignation);
+        assert_eq!(&message[..32], match_key.as_ref());
+        assert_eq!(&message[32..64], winner.as_ref());
+        assert_eq!(message[64], EndReason::Resignation as u8);
+        assert_eq!(message[65..], match_account.nonce.to_le_bytes());
+        
+        // A result signed for one game cannot settle the rematch
+        match_account.game_index = 1;
+        match_account.nonce = match_account.game_nonce();
+        assert_ne!(match_account.result_message(&match_key, &winner, EndReason::Resignation), message);
+    }
+}
@@ -0,0 +1,341 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::system_program;
+use spl_token_2022::extension::transfer_fee::{instruction::harvest_withheld_tokens_to_mint, TransferFeeAmount, TransferFeeConfig};
+use spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use spl_token_2022::state::{Account as TokenAccount, Mint};
+use crate::state::TokenFeeVault;
+use crate::errors::EscrowError;
+
+/// Accounts for matches staked in an SPL Token or Token-2022 mint.
+/// SOL matches leave them all out.
+#[derive(Accounts)]
+pub struct TokenAccounts<'info> {
+    /// CHECK: SPL Token or Token-2022 program, checked by `check_token_program`
+    pub token_program: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Stake mint, checked against the match
+    pub mint: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Token account owned by the escrow PDA, seeds checked against the match
+    #[account(mut)]
+    pub token_escrow: Option<UncheckedAccount<'info>>,
+    
+    /// Fee vault of the stake mint
+    #[account(mut)]
+    pub token_fee_vault: Option<Account<'info, TokenFeeVault>>,
+    
+    /// CHECK: Token account of the mint's fee vault, checked when fees are paid into it
+    #[account(mut)]
+    pub fee_vault_token: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Player A's token account, checked when used
+    #[account(mut)]
+    pub player_a_token: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Player B's token account, checked when used
+    #[account(mut)]
+    pub player_b_token: Option<UncheckedAccount<'info>>,
+}
+
+/// Require `token_program` to be SPL Token or Token-2022.
+pub fn check_token_program(token_program: &AccountInfo) -> Result<()> {
+    spl_token_2022::check_spl_token_program_account(token_program.key)?;
+    Ok(())
+}
+
+/// Decimals of `mint`, which must belong to `token_program`.
+pub fn mint_decimals(mint: &AccountInfo, token_program: &AccountInfo) -> Result<u8> {
+    require!(mint.owner == token_program.key, EscrowError::InvalidMint);
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&data)?;
+    Ok(mint.base.decimals)
+}
+
+/// Amount a sender has to transfer for `net_amount` to arrive after any
+/// Token-2022 transfer fee of the current epoch.
+pub fn gross_amount(mint: &AccountInfo, net_amount: u64) -> Result<u64> {
+    gross_amount_in_epoch(mint, Clock::get()?.epoch, net_amount)
+}
+
+/// `gross_amount` under the transfer fee of `epoch`.
+fn gross_amount_in_epoch(mint: &AccountInfo, epoch: u64, net_amount: u64) -> Result<u64> {
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&data)?;
+    let fee = match mint.get_extension::<TransferFeeConfig>() {
+        Ok(fee_config) => fee_config
+            .calculate_inverse_epoch_fee(epoch, net_amount)
+            .ok_or(EscrowError::ArithmeticOverflow)?,
+        Err(_) => 0,
+    };
+    
+    Ok(net_amount
+        .checked_add(fee)
+        .ok_or(EscrowError::ArithmeticOverflow)?)
+}
+
+/// Balance of `account`, which must be a `token_program` account of `mint` owned by `owner`.
+pub fn token_balance(
+    account: &AccountInfo,
+    token_program: &AccountInfo,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> Result<u64> {
+    require!(account.owner == token_program.key, EscrowError::InvalidTokenAccount);
+    let data = account.try_borrow_data()?;
+    let state = StateWithExtensions::<TokenAccount>::unpack(&data)?;
+    require!(
+        state.base.mint == *mint && state.base.owner == *owner,
+        EscrowError::InvalidTokenAccount
+    );
+    Ok(state.base.amount)
+}
+
+/// Create a token account of `mint` at a PDA, sized for the extensions the mint requires.
+pub fn create_token_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    account_seeds: &[&[u8]],
+    mint: &AccountInfo<'info>,
+    authority: &Pubkey,
+    token_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let space = {
+        let data = mint.try_borrow_data()?;
+        let mint_state = StateWithExtensions::<Mint>::unpack(&data)?;
+        let account_extensions =
+            ExtensionType::get_required_init_account_extensions(&mint_state.get_extension_types()?);
+        ExtensionType::get_account_len::<TokenAccount>(&account_extensions)
+    };
+    let lamports = Rent::get()?.minimum_balance(space);
+    let current_lamports = account.lamports();
+    
+    if current_lamports == 0 {
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                &[account_seeds],
+            ),
+            lamports,
+            space as u64,
+            token_program.key,
+        )?;
+    } else {
+        // Someone pre-funded the address, so top it up and take it over instead
+        if lamports > current_lamports {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    system_program::Transfer {
+                        from: payer.clone(),
+                        to: account.clone(),
+                    },
+                ),
+                lamports - current_lamports,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::Allocate {
+                    account_to_allocate: account.clone(),
+                },
+                &[account_seeds],
+            ),
+            space as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::Assign {
+                    account_to_assign: account.clone(),
+                },
+                &[account_seeds],
+            ),
+            token_program.key,
+        )?;
+    }
+    
+    let ix = spl_token_2022::instruction::initialize_account3(
+        token_program.key,
+        account.key,
+        mint.key,
+        authority,
+    )?;
+    invoke(&ix, &[account.clone(), mint.clone(), token_program.clone()])?;
+    
+    Ok(())
+}
+
+/// Close an empty token account owned by a PDA, first harvesting any withheld
+/// Token-2022 transfer fees to the mint so the account can be closed.
+pub fn close_token_account<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    authority_seeds: &[&[u8]],
+) -> Result<()> {
+    let has_withheld_fees = {
+        let data = account.try_borrow_data()?;
+        let state = StateWithExtensions::<TokenAccount>::unpack(&data)?;
+        match state.get_extension::<TransferFeeAmount>() {
+            Ok(fee_amount) => u64::from(fee_amount.withheld_amount) > 0,
+            Err(_) => false,
+        }
+    };
+    if has_withheld_fees {
+        let ix = harvest_withheld_tokens_to_mint(token_program.key, mint.key, &[account.key])?;
+        invoke(&ix, &[mint.clone(), account.clone(), token_program.clone()])?;
+    }
+    
+    let ix = spl_token_2022::instruction::close_account(
+        token_program.key,
+        account.key,
+        destination.key,
+        authority.key,
+        &[],
+    )?;
+    invoke_signed(
+        &ix,
+        &[account.clone(), destination.clone(), authority.clone(), token_program.clone()],
+        &[authority_seeds],
+    )?;
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spl_token_2022::extension::transfer_fee::TransferFee;
+    use spl_token_2022::extension::StateWithExtensionsMut;
+    use spl_token_2022::state::AccountState;
+    
+    /// Epoch from which `mint_data`'s newer transfer fee applies.
+    const NEWER_FEE_EPOCH: u64 = 10;
+    
+    fn transfer_fee(epoch: u64, basis_points: u16, maximum_fee: u64) -> TransferFee {
+        TransferFee {
+            epoch: epoch.into(),
+            maximum_fee: maximum_fee.into(),
+            transfer_fee_basis_points: basis_points.into(),
+        }
+    }
+    
+    /// Mint data, with a transfer fee of `older` until `NEWER_FEE_EPOCH` and `newer` after it.
+    fn mint_data(fees: Option<(TransferFee, TransferFee)>) -> Vec<u8> {
+        let extensions = if fees.is_some() { vec![ExtensionType::TransferFeeConfig] } else { vec![] };
+        let mut data = vec![0u8; ExtensionType::get_account_len::<Mint>(&extensions)];
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        state.base = Mint { decimals: 6, is_initialized: true, ..Mint::default() };
+        state.pack_base();
+        if let Some((older, newer)) = fees {
+            let config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+            config.older_transfer_fee = older;
+            config.newer_transfer_fee = newer;
+            state.init_account_type().unwrap();
+        }
+        data
+    }
+    
+    /// Token-2022 account data with the transfer fee extension a fee mint requires.
+    fn token_account_data(mint: Pubkey, owner: Pubkey, amount: u64) -> Vec<u8> {
+        let mut data = vec![0u8; ExtensionType::get_account_len::<TokenAccount>(&[ExtensionType::TransferFeeAmount])];
+        let mut state = StateWithExtensionsMut::<TokenAccount>::unpack_uninitialized(&mut data).unwrap();
+        state.base = TokenAccount { mint, owner, amount, state: AccountState::Initialized, ..TokenAccount::default() };
+        state.pack_base();
+        state.init_extension::<TransferFeeAmount>(true).unwrap();
+        state.init_account_type().unwrap();
+        data
+    }
+    
+    fn with_account<R>(mut data: Vec<u8>, owner: &Pubkey, f: impl FnOnce(&AccountInfo) -> R) -> R {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let account = AccountInfo::new(&key, false, true, &mut lamports, &mut data, owner, false, 0);
+        f(&account)
+    }
+    
+    fn program_account<'a>(key: &'a Pubkey, lamports: &'a mut u64) -> AccountInfo<'a> {
+        AccountInfo::new(key, false, false, lamports, &mut [], key, true, 0)
+    }
+    
+    fn gross(fees: Option<(TransferFee, TransferFee)>, epoch: u64, net_amount: u64) -> u64 {
+        with_account(mint_data(fees), &spl_token_2022::id(), |mint| {
+            gross_amount_in_epoch(mint, epoch, net_amount).unwrap()
+        })
+    }
+    
+    #[test]
+    fn gross_amount_without_transfer_fee_is_net_amount() {
+        assert_eq!(gross(None, 0, 1_000), 1_000);
+    }
+    
+    #[test]
+    fn gross_amount_covers_transfer_fee() {
+        let fee = transfer_fee(0, 100, u64::MAX); // 1%
+        let fees = Some((fee, transfer_fee(NEWER_FEE_EPOCH, 100, u64::MAX)));
+        assert_eq!(gross(fees, 0, 990), 1_000);
+        
+        // Whatever the amount, the fee charged on the gross leaves exactly the net amount
+        for net_amount in [1, 99, 12_345, 1_000_000_007] {
+            let gross_amount = gross(fees, 0, net_amount);
+            assert_eq!(gross_amount - fee.calculate_fee(gross_amount).unwrap(), net_amount);
+        }
+    }
+    
+    #[test]
+    fn gross_amount_respects_maximum_fee() {
+        let fees = Some((transfer_fee(0, 500, 10), transfer_fee(NEWER_FEE_EPOCH, 500, 10)));
+        assert_eq!(gross(fees, 0, 1_000_000), 1_000_010);
+    }
+    
+    #[test]
+    fn gross_amount_uses_fee_of_epoch() {
+        let fees = Some((transfer_fee(0, 0, 0), transfer_fee(NEWER_FEE_EPOCH, 1_000, u64::MAX)));
+        assert_eq!(gross(fees, NEWER_FEE_EPOCH - 1, 900), 900);
+        assert_eq!(gross(fees, NEWER_FEE_EPOCH, 900), 1_000);
+    }
+    
+    #[test]
+    fn fee_vault_balance_change_is_fee_net_of_transfer_fee() {
+        // `StakeVault::pay_fee` records the fee vault's balance change, not the amount sent
+        let fee = transfer_fee(0, 250, u64::MAX); // 2.5%
+        let (mint, vault) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let program = spl_token_2022::id();
+        let mut lamports = 0;
+        let token_program = program_account(&program, &mut lamports);
+        let balance = |amount| {
+            with_account(token_account_data(mint, vault, amount), &program, |account| {
+                token_balance(account, &token_program, &mint, &vault).unwrap()
+            })
+        };
+        
+        let sent = 10_000;
+        let before = balance(500);
+        let after = balance(500 + sent - fee.calculate_fee(sent).unwrap());
+        assert_eq!(after - before, 9_750);
+    }
+    
+    #[test]
+    fn token_balance_checks_program_mint_and_owner() {
+        let (mint, owner) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (program, other_program) = (spl_token_2022::id(), Pubkey::new_unique());
+        let (mut lamports, mut other_lamports) = (0, 0);
+        let token_program = program_account(&program, &mut lamports);
+        let other_token_program = program_account(&other_program, &mut other_lamports);
+        with_account(token_account_data(mint, owner, 42), &program, |account| {
+            assert_eq!(token_balance(account, &token_program, &mint, &owner).unwrap(), 42);
+            assert!(token_balance(account, &token_program, &Pubkey::new_unique(), &owner).is_err());
+            assert!(token_balance(account, &token_program, &mint, &Pubkey::new_unique()).is_err());
+            assert!(token_balance(account, &other_token_program, &mint, &owner).is_err());
+        });
+    }
+}